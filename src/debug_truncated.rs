@@ -0,0 +1,28 @@
+use core::fmt::{Debug, Formatter, Result};
+
+/// Formats at most `max` items as a list, followed by the number of the remaining items.
+pub struct DebugTruncated<'a, T> {
+    pub items: &'a [T],
+    pub max: usize,
+}
+
+impl<'a, T> DebugTruncated<'a, T> {
+    pub fn new(items: &'a [T], max: usize) -> Self {
+        Self {
+            items,
+            max,
+        }
+    }
+}
+
+impl<T: Debug> Debug for DebugTruncated<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (shown, rest) = self.items.split_at(self.max.min(self.items.len()));
+        let mut list = f.debug_list();
+        list.entries(shown);
+        if !rest.is_empty() {
+            list.entry(&format_args!("... ({} more)", rest.len()));
+        }
+        list.finish()
+    }
+}
@@ -0,0 +1,71 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::NonZeroUsize;
+use serde::de::{SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes a sequence and extends `target` with its elements in a single `extend` call, without collecting them into a temporary `Vec`.
+///
+/// On error, the elements deserialized before the error remain in `target`.
+pub fn deserialize_extend<'de, D: Deserializer<'de>, T: Deserialize<'de>, E: Extend<T>>(deserializer: D, target: &mut E) -> Result<(), D::Error> {
+    deserializer.deserialize_seq(ExtendVisitor {
+        target,
+        marker: PhantomData,
+    })
+}
+
+struct ExtendVisitor<'a, T, E> {
+    target: &'a mut E,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>, E: Extend<T>> Visitor<'de> for ExtendVisitor<'_, T, E> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<(), A::Error> {
+        let mut error = None;
+        self.target.extend(SeqIter {
+            seq,
+            error: &mut error,
+            marker: PhantomData,
+        });
+        error.map_or(Ok(()), Err)
+    }
+}
+
+/// The maximum number of bytes that the size hint of [`SeqIter`] reserves for, like the cautious size hint of `serde`, so a malicious length doesn't cause a huge allocation.
+const MAX_PREALLOCATION: usize = 1024 * 1024;
+
+/// Yields the elements of a sequence until the end or the first error (which is stored in `error`), and forwards the length hint of the sequence, so `extend` can reserve the capacity.
+struct SeqIter<'a, 'de, A: SeqAccess<'de>, T> {
+    seq: A,
+    error: &'a mut Option<A::Error>,
+    marker: PhantomData<(&'de (), T)>,
+}
+
+impl<'de, A: SeqAccess<'de>, T: Deserialize<'de>> Iterator for SeqIter<'_, 'de, A, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.error.is_some() {
+            return None;
+        }
+        self.seq.next_element().unwrap_or_else(|error| {
+            *self.error = Some(error);
+            None
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.error.is_some() {
+            return (0, Some(0));
+        }
+        let len = self.seq.size_hint().unwrap_or(0);
+        let len = NonZeroUsize::new(size_of::<T>()).map_or(len, |size| len.min(MAX_PREALLOCATION / size));
+        (len, None)
+    }
+}
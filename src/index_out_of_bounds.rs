@@ -0,0 +1,26 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result};
+
+/// The error returned by the generated `try_get` method when the index is out of bounds.
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct IndexOutOfBounds {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl IndexOutOfBounds {
+    pub fn new(index: usize, len: usize) -> Self {
+        Self {
+            index,
+            len,
+        }
+    }
+}
+
+impl Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "index {} is out of bounds for length {}", self.index, self.len)
+    }
+}
+
+impl Error for IndexOutOfBounds {}
@@ -113,198 +113,4034 @@
 //! - Is `#[repr(transparent)]` for zero-cost abstraction
 //! - Implements `Deref` and `DerefMut` to `Vec<T>` for access to all Vec methods
 //! - Provides `new()`, `push()`, and `extend_from()` methods
-//! - Implements `Default`, `Extend`, `IntoIterator`, `From<Vec<T>>`, and `Into<Vec<T>>`
+//! - Implements `Default`, `Extend`, `FromIterator`, `IntoIterator`, `From<Vec<T>>`, and `Into<Vec<T>>` (the conversions never reallocate)
+//! - Implements `Into<Result<(), Vec<T>>>`, which maps an empty collection to `Ok(())`
 //! - Supports automatic conversions from variant types when using the `variants = [...]` option
+//! - Generates a `VariantKind` tag enum and variant-aware methods when the variants are named (`variants = [Kind(Payload), ...]`)
 //!
-//! # Custom Derives
+//! # Named variants
 //!
-//! You can add any derive macros to your struct definition, and they will be applied to
-//! the generated struct. For example:
+//! The `variants` option also accepts the enum variant names together with their payload types.
+//! In this form, the macro generates a fieldless `{Name}VariantKind` enum (with the same visibility as the wrapper) and the variant-aware methods.
+//! The list must contain every variant of the enum, and every variant must be a single-field tuple variant.
+//!
+//! The `VariantKind` enum derives `Debug, Clone, Copy, PartialEq, Eq, Hash` by default.
+//! Use the `variant_kind_derives = [...];` option (right after the `variants` option) to replace this list, e.g. `variant_kind_derives = [Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize];`.
+//!
+//! ```rust
+//! # use derive_more::From;
+//! #
+//! # #[derive(Debug)]
+//! # pub struct PasswordMinLengthError;
+//! #
+//! # #[derive(Debug)]
+//! # pub struct InvalidEmailError;
+//! #
+//! #[derive(From, Debug)]
+//! pub enum ValidationError {
+//!     PasswordMinLength(PasswordMinLengthError),
+//!     InvalidEmail(InvalidEmailError),
+//! }
+//!
+//! vec_of_enum::define!(
+//!     pub struct ValidationErrors(Vec<ValidationError>);
+//!     variants = [PasswordMinLength(PasswordMinLengthError), InvalidEmail(InvalidEmailError)];
+//! );
+//!
+//! let mut errors = ValidationErrors::default();
+//! errors.push(InvalidEmailError);
+//! errors.push(PasswordMinLengthError);
+//!
+//! let kinds = errors.iter_tagged().map(|(kind, _)| kind).collect::<Vec<_>>();
+//! assert_eq!(kinds, [ValidationErrorsVariantKind::InvalidEmail, ValidationErrorsVariantKind::PasswordMinLength]);
+//! ```
+//!
+//! # Metrics
+//!
+//! With the `metrics` feature enabled, the `metric = "name";` option increments the [`metrics`](https://docs.rs/metrics) counter with this name once per element that enters the wrapper: the same elements that the [push hook](#push-hook) is called with (e.g. the elements added by `push`, `extend_from`, the `Extend` and `FromIterator` impls, `deserialize_extend`, `new` and `From<Vec>`).
+//! The option must come after the `variants` option (if any). Using it without the feature is a compile error.
+//!
+//! ```rust
+//! # #[cfg(feature = "metrics")]
+//! # {
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//!
+//! use metrics::{Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+//!
+//! #[derive(Default)]
+//! pub struct Total(AtomicU64);
+//!
+//! impl CounterFn for Total {
+//!     fn increment(&self, value: u64) {
+//!         self.0.fetch_add(value, Ordering::Relaxed);
+//!     }
+//!
+//!     fn absolute(&self, value: u64) {
+//!         self.0.store(value, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! #[derive(Default)]
+//! pub struct TotalRecorder(Arc<Total>);
+//!
+//! impl Recorder for TotalRecorder {
+//!     fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+//!
+//!     fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+//!
+//!     fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+//!
+//!     fn register_counter(&self, _key: &Key, _metadata: &Metadata<'_>) -> Counter {
+//!         Counter::from_arc(self.0.clone())
+//!     }
+//!
+//!     fn register_gauge(&self, _key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+//!         Gauge::noop()
+//!     }
+//!
+//!     fn register_histogram(&self, _key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+//!         Histogram::noop()
+//!     }
+//! }
+//!
+//! vec_of_enum::define!(
+//!     pub struct Messages(Vec<String>);
+//!     metric = "messages_total";
+//! );
+//!
+//! let recorder = TotalRecorder::default();
+//! let total = || recorder.0.0.swap(0, Ordering::Relaxed);
+//! metrics::with_local_recorder(&recorder, || {
+//!     let mut messages = Messages::default();
+//!     messages.push("hello");
+//!     messages.extend_from(["foo", "bar"]);
+//! });
+//! assert_eq!(total(), 3);
+//!
+//! metrics::with_local_recorder(&recorder, || {
+//!     let mut messages: Messages = ["a", "b"].into_iter().collect();
+//!     messages.extend(["c".to_string()]);
+//!     let _messages = Messages::new(vec!["d".to_string()]);
+//! });
+//! assert_eq!(total(), 4);
+//! # }
+//! ```
+//!
+//! # Serde
+//!
+//! The wrapper can derive `Serialize` and `Deserialize` like any other struct, which represents it as a sequence.
+//! However, `#[serde(flatten)]` requires the flattened field to be represented as a map, so flattening such a wrapper into a parent struct fails at runtime.
+//!
+//! With the `serde` feature enabled, the `serde_flatten_as = "field";` option generates `Serialize` and `Deserialize` impls that represent the wrapper as a single-field map (`{ "field": [...] }`), which is compatible with `#[serde(flatten)]`.
+//! Don't derive `Serialize` or `Deserialize` together with this option. The option must come after the `metric` option (if any).
 //!
 //! ```rust
+//! # #[cfg(feature = "serde")]
+//! # {
 //! use serde::{Deserialize, Serialize};
 //!
-//! #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-//! pub enum MyEnum {}
+//! vec_of_enum::define!(
+//!     #[derive(Debug, PartialEq)]
+//!     pub struct Messages(Vec<String>);
+//!     serde_flatten_as = "messages";
+//! );
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! pub struct Report {
+//!     id: u64,
+//!     #[serde(flatten)]
+//!     messages: Messages,
+//! }
+//!
+//! let report = Report {
+//!     id: 1,
+//!     messages: Messages::new(vec!["hello".to_string()]),
+//! };
+//! let json = serde_json::to_string(&report)?;
+//! assert_eq!(json, r#"{"id":1,"messages":["hello"]}"#);
+//! assert_eq!(serde_json::from_str::<Report>(&json)?, report);
+//! # }
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! The generated impls name the struct after the wrapper, which is visible in the formats that encode the struct names and in the error messages.
+//! The `serde_rename = "Name";` option overrides the name. It's valid only together with the `serde_flatten_as` option; with derived impls, add `#[serde(rename = "Name")]` to the struct attributes instead.
+//! The option must come after the `ord_by` option (if any).
+//!
+//! ```rust
+//! # #[cfg(feature = "serde")]
+//! # {
+//! vec_of_enum::define!(
+//!     #[derive(Debug)]
+//!     pub struct Messages(Vec<String>);
+//!     serde_flatten_as = "messages";
+//!     serde_rename = "MessageList";
+//! );
+//!
+//! let error = serde_json::from_str::<Messages>("1").err().ok_or("a number deserialized into the collection")?;
+//! assert!(error.to_string().contains("expected struct MessageList with field `messages`"));
+//! # }
+//! # Ok::<(), &'static str>(())
+//! ```
+//!
+//! The `serialize_grouped;` option (requires named variants) generates a `Serialize` impl that represents the collection as a map from the variant names to the arrays of the payloads, in the order of the `variants` option. Every variant has an entry, so the variants without elements are represented as empty arrays.
+//! The elements keep their order within every group. `Deserialize` isn't generated, so don't derive `Serialize` with this option, and derive `Deserialize` only if the flat representation is expected as input.
+//! The option must come after the `into` option (if any).
+//!
+//! ```rust
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use derive_more::From;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! pub struct PasswordMinLengthError {
+//!     min_length: usize,
+//! }
+//!
+//! #[derive(Serialize)]
+//! pub struct InvalidEmailError {
+//!     email: String,
+//! }
+//!
+//! #[derive(From)]
+//! pub enum ValidationError {
+//!     PasswordMinLength(PasswordMinLengthError),
+//!     InvalidEmail(InvalidEmailError),
+//! }
 //!
 //! vec_of_enum::define!(
-//!     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-//!     pub struct MyVec(Vec<MyEnum>);
+//!     pub struct ValidationErrors(Vec<ValidationError>);
+//!     variants = [PasswordMinLength(PasswordMinLengthError), InvalidEmail(InvalidEmailError)];
+//!     serialize_grouped;
 //! );
+//!
+//! let mut errors = ValidationErrors::default();
+//! errors.push(InvalidEmailError { email: "a@example.com".into() });
+//! errors.push(InvalidEmailError { email: "b@example.com".into() });
+//! assert_eq!(serde_json::to_string(&errors)?, r#"{"PasswordMinLength":[],"InvalidEmail":[{"email":"a@example.com"},{"email":"b@example.com"}]}"#);
+//!
+//! errors.push(PasswordMinLengthError { min_length: 8 });
+//! assert_eq!(serde_json::to_string(&errors)?, r#"{"PasswordMinLength":[{"min_length":8}],"InvalidEmail":[{"email":"a@example.com"},{"email":"b@example.com"}]}"#);
+//! # }
+//! # Ok::<(), serde_json::Error>(())
 //! ```
 //!
-//! This allows you to add any necessary derives that your application requires.
+//! # Ring buffer
+//!
+//! The `ring_buffer = N;` option turns the wrapper into a collection of the most recent `N` elements: `push`, `extend_from`, `extend_from_hinted`, `extend_sorted`, the `Extend` and `FromIterator` impls and `from_exact_iter` remove the oldest elements when the length exceeds `N` (so do the methods built on them, e.g. `push_fmt` and `deserialize_extend`).
+//! The eviction shifts the remaining elements, so `push` takes O(N) time. The other methods evict in batches while consuming the iterator, so they take O(1) amortized time per element, and the wrapper never holds more than `2 * N` elements even if the iterator is long or infinite. Other ways of adding the elements (`new`, `From<Vec>`, `From<VecDeque>`, `concat_all`, `replace_with`, `from_raw_parts`, a derived `Deserialize` and `DerefMut`) don't enforce the bound.
+//! The option must come after the `serde_flatten_as` option (if any).
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     pub struct RecentMessages(Vec<String>);
+//!     ring_buffer = 2;
+//! );
+//!
+//! let mut messages = RecentMessages::default();
+//! messages.push("a");
+//! messages.push("b");
+//! messages.push("c");
+//! assert_eq!(messages.as_slice(), ["b", "c"]);
+//!
+//! messages.extend_from(["d", "e", "f"]);
+//! assert_eq!(messages.as_slice(), ["e", "f"]);
+//!
+//! messages.extend((0..1000).map(|index| index.to_string()));
+//! assert_eq!(messages.as_slice(), ["998", "999"]);
+//!
+//! let collected: RecentMessages = (0..100).map(|index| index.to_string()).collect();
+//! assert_eq!(collected.as_slice(), ["98", "99"]);
+//!
+//! let exact = RecentMessages::from_exact_iter((0..100).map(|index| index.to_string()));
+//! assert_eq!(exact.as_slice(), ["98", "99"]);
+//! ```
+//!
+//! # Comparing different wrappers
+//!
+//! The `compat_eq = [OtherWrapper, ...];` option generates `PartialEq` impls in both directions between the wrapper and other wrappers over the same inner type (requires the inner type to implement `PartialEq`).
+//! Use the option on only one side of every pair, because using it on both sides generates conflicting impls.
+//! The option must come after the `ring_buffer` option (if any).
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     pub struct InputMessages(Vec<String>);
+//! );
+//!
+//! vec_of_enum::define!(
+//!     pub struct OutputMessages(Vec<String>);
+//!     compat_eq = [InputMessages];
+//! );
+//!
+//! let input = InputMessages::new(vec!["hello".to_string()]);
+//! let output = OutputMessages::new(vec!["hello".to_string()]);
+//! assert!(output == input);
+//! assert!(input == output);
+//! ```
+//!
+//! # Paired elements
+//!
+//! The `paired;` option generates the key-aware methods for wrappers over key-value pairs (2-tuples), which turns the wrapper into a lightweight multimap that preserves the insertion order.
+//! The option must come after the `compat_eq` option (if any).
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     pub struct FieldErrors(Vec<(&'static str, String)>);
+//!     paired;
+//! );
+//!
+//! let mut errors = FieldErrors::default();
+//! errors.push(("email", "is blocked".to_string()));
+//! errors.push(("password", "is too short".to_string()));
+//! errors.push(("email", "is too long".to_string()));
+//!
+//! assert_eq!(errors.keys().collect::<Vec<_>>(), [&"email", &"password", &"email"]);
+//! assert_eq!(errors.errors_for(&"email").collect::<Vec<_>>(), ["is blocked", "is too long"]);
+//! ```
+//!
+//! # Truncated debug output
+//!
+//! The `debug_max = N;` option generates a `Debug` impl that prints at most `N` elements, followed by the number of the remaining elements, which keeps the logs bounded for large collections.
+//! It requires the inner type to implement `Debug`. Don't derive `Debug` together with this option. The option must come after the `paired` option (if any).
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     pub struct Numbers(Vec<u32>);
+//!     debug_max = 2;
+//! );
+//!
+//! let numbers = Numbers::new(vec![1, 2, 3, 4, 5]);
+//! assert_eq!(format!("{numbers:?}"), "Numbers([1, 2, ... (3 more)])");
+//! ```
+//!
+//! # Conversions into a plain `Vec`
+//!
+//! The `vec_from_variants;` option additionally generates `impl From<Variant> for Vec<T>` for every variant type, so a single value converts into a plain `Vec` for the APIs that take it directly.
+//! The impls are opt-in because the orphan rule only allows them for the variant types defined in the calling crate, and because two wrappers over the same element type can't both generate them.
+//! The option requires the `variants` option and must come after the `debug_max` option (if any).
+//!
+//! ```rust
+//! # use derive_more::From;
+//! #
+//! # #[derive(Debug)]
+//! # pub struct InvalidEmailError;
+//! #
+//! #[derive(From, Debug)]
+//! pub enum ValidationError {
+//!     InvalidEmail(InvalidEmailError),
+//! }
+//!
+//! vec_of_enum::define!(
+//!     pub struct ValidationErrors(Vec<ValidationError>);
+//!     variants = [InvalidEmail(InvalidEmailError)];
+//!     vec_from_variants;
+//! );
+//!
+//! fn report(errors: Vec<ValidationError>) -> usize {
+//!     errors.len()
+//! }
+//!
+//! assert_eq!(report(InvalidEmailError.into()), 1);
+//! ```
+//!
+//! # Warning on drop
+//!
+//! With the `tracing` feature enabled, the `warn_on_drop;` option generates a `Drop` impl that emits a `tracing` warning when a non-empty collection is dropped, which helps to find the errors that were collected but never surfaced.
+//! The warning is emitted only for non-empty collections. The overhead is a length check on every drop.
+//! The consuming methods (`into_iter`, `Into<Vec<T>>`, etc.) take the elements out, so a consumed collection doesn't trigger the warning.
+//! Since the wrapper implements `Drop`, its field can't be moved out with a pattern; use the consuming methods instead.
+//! The option must come after the `vec_from_variants` option (if any).
+//!
+//! ```rust
+//! # #[cfg(feature = "tracing")]
+//! # {
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! use tracing::span::{Attributes, Id, Record};
+//! use tracing::{Event, Level, Metadata, Subscriber};
+//!
+//! #[derive(Clone, Default)]
+//! pub struct WarningCounter(Arc<AtomicUsize>);
+//!
+//! impl Subscriber for WarningCounter {
+//!     fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+//!         true
+//!     }
+//!
+//!     fn new_span(&self, _span: &Attributes<'_>) -> Id {
+//!         Id::from_u64(1)
+//!     }
+//!
+//!     fn record(&self, _span: &Id, _values: &Record<'_>) {}
+//!
+//!     fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+//!
+//!     fn event(&self, event: &Event<'_>) {
+//!         if *event.metadata().level() == Level::WARN {
+//!             self.0.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     }
+//!
+//!     fn enter(&self, _span: &Id) {}
+//!
+//!     fn exit(&self, _span: &Id) {}
+//! }
+//!
+//! vec_of_enum::define!(
+//!     pub struct Messages(Vec<String>);
+//!     warn_on_drop;
+//! );
+//!
+//! let warnings = WarningCounter::default();
+//! tracing::subscriber::with_default(warnings.clone(), || {
+//!     let mut messages = Messages::default();
+//!     messages.push("hello".to_string());
+//!     drop(messages);
+//! });
+//! assert_eq!(warnings.0.swap(0, Ordering::Relaxed), 1);
+//!
+//! tracing::subscriber::with_default(warnings.clone(), || {
+//!     let mut messages = Messages::default();
+//!     messages.push("hello".to_string());
+//!     // Consuming the collection doesn't emit the warning
+//!     let messages: Vec<String> = messages.into();
+//!     assert_eq!(messages, ["hello"]);
+//!     // Neither does dropping an empty collection
+//!     drop(Messages::default());
+//! });
+//! assert_eq!(warnings.0.swap(0, Ordering::Relaxed), 0);
+//! # }
+//! ```
+//!
+//! # Attributes on generated impls
+//!
+//! The `impl_attrs = [...];` option applies the attributes to every generated impl, which allows suppressing a lint that the generated code trips in your crate without allowing it for the whole module.
+//! The attributes aren't applied to the generated types (the struct itself, `{Name}IntoIter` and `{Name}VariantKind`).
+//! The option must come after the `warn_on_drop` option (if any).
+//!
+//! ```rust
+//! # use derive_more::From;
+//! #
+//! # #[derive(Debug)]
+//! # pub struct InvalidEmailError;
+//! #
+//! #[derive(From, Debug)]
+//! pub enum ValidationError {
+//!     InvalidEmail(InvalidEmailError),
+//! }
+//!
+//! vec_of_enum::define!(
+//!     #[derive(Debug)]
+//!     pub struct ValidationErrors(Vec<ValidationError>);
+//!     variants = [InvalidEmail(InvalidEmailError)];
+//!     impl_attrs = [#[allow(clippy::must_use_candidate)], #[allow(clippy::missing_inline_in_public_items)]];
+//! );
+//!
+//! let mut errors = ValidationErrors::default();
+//! errors.push(InvalidEmailError);
+//! assert!(errors.is_only_variant(ValidationErrorsVariantKind::InvalidEmail));
+//! let iter: ValidationErrorsIntoIter = errors.into_iter();
+//! assert_eq!(iter.len(), 1);
+//! ```
+//!
+//! The attributes take effect on the generated impls: for example, `#[cfg(any())]` removes all of them, so the wrapper has no `push` method, while the struct itself is still defined.
+//!
+//! ```rust,compile_fail,E0599
+//! vec_of_enum::define!(
+//!     pub struct Messages(Vec<String>);
+//!     impl_attrs = [#[cfg(any())]];
+//! );
+//!
+//! let mut messages = Messages(Vec::new());
+//! messages.push("hello");
+//! ```
+//!
+//! # Push hook
+//!
+//! The `on_push = fn;` option calls the function (a `fn(&T)`, e.g. a function path or a non-capturing closure) exactly once per element that enters the wrapper, which allows logging or waking a listener whenever an error is recorded.
+//! The elements enter through the constructors and conversions (`new`, `from_exact_iter`, `FromIterator`, `From<Vec>`, `From<VecDeque>`, the `From` impls generated by the `variants` option) and the appending methods (`push`, `push_fmt`, `extend_from`, `extend_from_hinted`, `extend_sorted`, `extend_until_variant`, `deserialize_extend` and the `Extend` impl). The summary element of `summarize` enters the wrapper too.
+//! The methods that move or clone the elements that are already in the wrappers (e.g. `concat_all`, `into_batches`, `into_sorted`, `clone_into`) don't call it again. The ways of modifying the inner `Vec` directly (`DerefMut`, `replace_with`, `from_raw_parts` and a derived `Deserialize`) bypass it.
+//! The hook is called before the element is added. Since it's called per element, extending with a large iterator calls it that many times; keep it cheap.
+//! The option must come after the `impl_attrs` option (if any).
+//!
+//! ```rust
+//! use std::collections::VecDeque;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! static RECORDED: AtomicUsize = AtomicUsize::new(0);
+//!
+//! fn record(_message: &String) {
+//!     RECORDED.fetch_add(1, Ordering::Relaxed);
+//! }
+//!
+//! fn recorded() -> usize {
+//!     RECORDED.swap(0, Ordering::Relaxed)
+//! }
+//!
+//! vec_of_enum::define!(
+//!     pub struct Messages(Vec<String>);
+//!     on_push = record;
+//! );
+//!
+//! let mut messages = Messages::default();
+//! messages.push("a");
+//! assert_eq!(recorded(), 1);
+//! messages.push_fmt(format_args!("{}", 1));
+//! assert_eq!(recorded(), 1);
+//! messages.extend_from(["b", "c"]);
+//! assert_eq!(recorded(), 2);
+//! messages.extend_from_hinted(2, ["d", "e"]);
+//! assert_eq!(recorded(), 2);
+//! messages.extend(["f".to_string()]);
+//! assert_eq!(recorded(), 1);
+//! assert_eq!(messages.len(), 7);
+//!
+//! let mut sorted = Messages::new(vec!["a".to_string(), "c".to_string()]);
+//! assert_eq!(recorded(), 2);
+//! sorted.extend_sorted(["b"]);
+//! assert_eq!(recorded(), 1);
+//!
+//! let collected: Messages = ["a", "b"].into_iter().collect();
+//! assert_eq!(recorded(), 2);
+//! let exact = Messages::from_exact_iter(["a", "b"]);
+//! assert_eq!(recorded(), 2);
+//! let from_vec = Messages::from(vec!["a".to_string()]);
+//! assert_eq!(recorded(), 1);
+//! let from_deque = Messages::from(VecDeque::from(["a".to_string()]));
+//! assert_eq!(recorded(), 1);
+//!
+//! let sorted = sorted.into_sorted();
+//! assert_eq!(recorded(), 0);
+//! let sorted = sorted.into_sorted_by(|a, b| b.cmp(a));
+//! assert_eq!(recorded(), 0);
+//! let sorted = sorted.into_sorted_by_key(|message| message.len());
+//! assert_eq!(recorded(), 0);
+//!
+//! let concatenated = Messages::concat_all([messages, sorted, collected, exact, from_vec, from_deque]);
+//! assert_eq!(recorded(), 0);
+//! let mut cloned = Messages::default();
+//! concatenated.clone_into(&mut cloned);
+//! assert_eq!(recorded(), 0);
+//! assert_eq!(cloned.len(), 16);
+//!
+//! let batches = cloned.into_batches(4);
+//! assert_eq!(recorded(), 0);
+//! let rejoined = Messages::concat_all(batches);
+//! assert_eq!(recorded(), 0);
+//! let taken = rejoined.take_while_owned(|message| message != "f").drop_while(|message| message == "a");
+//! assert_eq!(recorded(), 0);
+//! let summary = taken.summarize(|messages| messages.concat());
+//! assert_eq!(recorded(), 1);
+//! assert_eq!(summary.len(), 1);
+//! ```
+//!
+//! # Ordering by length
+//!
+//! The `ord_by = len;` option generates `PartialOrd` and `Ord` impls that compare the lengths first, then the elements lexicographically (requires the inner type to implement `Ord`), so the collections with fewer elements sort first.
+//! The lexicographic order (without comparing the lengths first) is what `#[derive(PartialOrd, Ord)]` generates; don't combine the derive with this option, because the impls would conflict.
+//! The wrapper must derive `PartialEq` and `Eq`, which are consistent with this order.
+//! The option must come after the `on_push` option (if any).
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     #[derive(PartialEq, Eq, Debug)]
+//!     pub struct Problems(Vec<&'static str>);
+//!     ord_by = len;
+//! );
+//!
+//! let mut results = vec![
+//!     Problems::new(vec!["a", "b"]),
+//!     Problems::new(vec!["c"]),
+//!     Problems::new(vec!["a", "a"]),
+//! ];
+//! results.sort();
+//! assert_eq!(results, [Problems::new(vec!["c"]), Problems::new(vec!["a", "a"]), Problems::new(vec!["a", "b"])]);
+//! assert!(Problems::new(vec!["z"]) < Problems::new(vec!["a", "a"]));
+//! ```
+//!
+//! # Conversions into a custom type
+//!
+//! The `into = Target => function;` option generates `impl From<Wrapper> for Target`, which passes the elements to the function (a `fn(Vec<T>) -> Target`, e.g. a function path or a non-capturing closure), so the wrapper converts directly into a richer type (e.g. a report with the errors and some metadata).
+//! The target type is required because the impl can't be inferred from the function.
+//! The wrapper is a local type, so the orphan rule allows any target type, including the types of other crates. However, the target can't be a type that the wrapper already converts into (`Vec<T>`, `VecDeque<T>` or `Result<(), Vec<T>>`), because the impls would conflict.
+//! The option must come after the `serde_rename` option (if any).
+//!
+//! ```rust
+//! pub struct Report {
+//!     errors: Vec<String>,
+//!     summary: String,
+//! }
+//!
+//! impl Report {
+//!     pub fn from_errors(errors: Vec<String>) -> Self {
+//!         let summary = format!("errors: {}", errors.len());
+//!         Self { errors, summary }
+//!     }
+//! }
+//!
+//! vec_of_enum::define!(
+//!     pub struct Errors(Vec<String>);
+//!     into = Report => Report::from_errors;
+//! );
+//!
+//! let report = Report::from(Errors::new(vec!["is blocked".to_string()]));
+//! assert_eq!(report.errors, ["is blocked"]);
+//! assert_eq!(report.summary, "errors: 1");
+//! ```
+//!
+//! # Validating the default
+//!
+//! The `Default` impl returns an empty collection, and `#[serde(default)]` on a field of the wrapper type relies on it, so a missing field deserializes into an empty collection. If the wrapper has an invariant that an empty collection violates (e.g. "at least one element"), the defaulted value is invalid.
+//! The `default_valid = validator;` option generates a `#[test]` that passes `Default::default()` to the validator (a function that takes a reference to the wrapper and returns `Result<(), E>` where `E: Debug`), which catches such a mismatch at test time. The test is named `{name}_default_is_valid` (in snake case) and is compiled only when testing the crate that calls `define!`.
+//! The option must come after the `serialize_grouped` option (if any).
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     #[derive(Debug)]
+//!     pub struct Messages(Vec<String>);
+//!     default_valid = validate;
+//! );
+//!
+//! #[derive(Debug)]
+//! pub struct BlankMessageError;
+//!
+//! /// The messages must not be blank (an empty collection is valid)
+//! pub fn validate(messages: &Messages) -> Result<(), BlankMessageError> {
+//!     if messages.iter().any(|message| message.trim().is_empty()) {
+//!         Err(BlankMessageError)
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! // The generated test calls the validator like this
+//! assert!(validate(&Messages::default()).is_ok());
+//! assert!(validate(&Messages::new(vec![" ".to_string()])).is_err());
+//! ```
+//!
+//! # Builder fields
+//!
+//! The wrapper implements `Default` and `Extend`, which is what the `each` setters of [`derive_builder`](https://docs.rs/derive_builder) require, so it can be used as a builder field without any adapter:
+//!
+//! ```rust
+//! use derive_builder::Builder;
+//!
+//! vec_of_enum::define!(
+//!     #[derive(Clone, Debug)]
+//!     pub struct Messages(Vec<String>);
+//! );
+//!
+//! #[derive(Builder, Debug)]
+//! pub struct Report {
+//!     #[builder(default, setter(each(name = "add", into)))]
+//!     messages: Messages,
+//! }
+//!
+//! let report = ReportBuilder::default().add("hello").add("world").build()?;
+//! assert_eq!(report.messages.as_slice(), ["hello", "world"]);
+//!
+//! let report = ReportBuilder::default().build()?;
+//! assert!(report.messages.is_empty());
+//! # Ok::<(), ReportBuilderError>(())
+//! ```
+//!
+//! # Generic wrappers
+//!
+//! The struct may have type parameters, which are passed to the generated impls together with the `where` clause.
+//! A generic wrapper gets the core API only: `new`, `push`, `extend_from` (and the other `impl_self` methods), `Default`, `Extend`, `FromIterator`, the `IntoIterator` impls, `Deref`, `DerefMut`, `AsRef<[T]>`, `AsMut<[T]>` and the conversions from and into `Vec<T>` and `VecDeque<T>`.
+//! The other options (except `impl_attrs` and `default_valid`) aren't supported; using the `variants` option is a compile error.
+//!
+//! ```rust
+//! vec_of_enum::define!(
+//!     #[derive(Debug)]
+//!     pub struct Items<T>(Vec<T>) where [T: Clone];
+//! );
+//!
+//! vec_of_enum::define!(
+//!     pub struct Numbers(Vec<u32>);
+//! );
+//!
+//! let mut items = Items::<String>::default();
+//! items.push("a");
+//! items.extend_from(["b", "c"]);
+//! assert_eq!((&items).into_iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+//!
+//! let vec: Vec<String> = items.into();
+//! let items = Items::from(vec);
+//! assert_eq!(items.into_iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+//!
+//! let items = [1u8, 2].into_iter().collect::<Items<u32>>();
+//! let numbers = Numbers::new(Vec::from(items));
+//! assert_eq!(numbers.as_slice(), [1, 2]);
+//! ```
+//!
+//! # `no_std`
+//!
+//! The crate is `no_std` and the generated code refers to `core` and `alloc` through the crate itself, so `define!` works in a `no_std` crate without any `alloc` import of its own (the macro matches the `Vec` of the struct definition as a token, so it doesn't need to be in scope).
+//! Disable the default `std` feature to avoid depending on `std`.
+//! The methods that track the seen keys in a `HashSet` or a `HashMap` (`dedup_by_variant_keys`, `dedup_global_by_key` and `find_duplicates`) require `std`; their `_ord` counterparts (`dedup_by_variant_keys_ord`, `dedup_global_by_key_ord` and `find_duplicates_ord`) use a `BTreeSet` or a `BTreeMap`, so they require `Ord` keys instead of `Hash` ones and are available without `std`.
+//!
+//! ```rust
+//! #![no_std]
+//!
+//! extern crate alloc;
+//!
+//! use derive_more::From;
+//!
+//! #[derive(From, Debug)]
+//! pub enum Problem {
+//!     Fatal(FatalError),
+//!     Warning(WarningError),
+//! }
+//!
+//! #[derive(Debug)]
+//! pub struct FatalError;
+//!
+//! #[derive(Debug)]
+//! pub struct WarningError;
+//!
+//! vec_of_enum::define!(
+//!     pub struct Numbers(Vec<u32>);
+//! );
+//!
+//! vec_of_enum::define!(
+//!     pub struct Problems(Vec<Problem>);
+//!     variants = [Fatal(FatalError), Warning(WarningError)];
+//! );
+//!
+//! fn main() {
+//!     let mut numbers = Numbers::default();
+//!     numbers.push(1u8);
+//!     numbers.extend_from([2u8, 3, 1]);
+//!     assert_eq!(numbers.dedup_global_by_key_ord(|number| *number), 1);
+//!     assert_eq!(numbers.as_slice(), [1, 2, 3]);
+//!
+//!     let problems = [WarningError, WarningError].into_iter().collect::<Problems>();
+//!     assert!(problems.is_only_warning());
+//! }
+//! ```
+//!
+//! # Cargo features
+//!
+//! - `std` (enabled by default): enables the `dedup_by_variant_keys`, `dedup_global_by_key` and `find_duplicates` methods (they use `HashSet` and `HashMap`)
+//! - `metrics`: enables the `metric` option
+//! - `rayon`: enables the `par_retain` and `par_into_filtered` methods (filter the elements in parallel)
+//! - `serde`: enables the `serde_flatten_as` and `serialize_grouped` options and the `deserialize_extend` method (deserializes a sequence into an existing collection)
+//! - `serde_json`: enables the `serde` feature and the `write_json_lines` method (writes the elements as newline-delimited JSON)
+//! - `tracing`: enables the `warn_on_drop` option
+//!
+//! # Custom Derives
+//!
+//! You can add any derive macros to your struct definition, and they will be applied to
+//! the generated struct. For example:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+//! pub enum MyEnum {}
+//!
+//! vec_of_enum::define!(
+//!     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+//!     pub struct MyVec(Vec<MyEnum>);
+//! );
+//! ```
+//!
+//! This allows you to add any necessary derives that your application requires.
+
+#![no_std]
+#![deny(clippy::arithmetic_side_effects)]
+#![cfg_attr(not(test), deny(unused_crate_dependencies))]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Generates the wrapper struct and its impls (see the crate docs for the options).
+///
+/// The generic parameters and the local type aliases of the generated code have reserved names (starting with `__VecOfEnum`), so they don't shadow the element types, whatever their names are:
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(From, PartialEq, Eq, PartialOrd, Ord, Debug)]
+/// pub enum Item {
+///     Number(u32),
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct Stop;
+///
+/// #[derive(From, Debug)]
+/// pub enum Iter {
+///     Number(u32),
+///     Stop(Stop),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Items(Vec<Item>);
+/// );
+///
+/// vec_of_enum::define!(
+///     pub struct Iters(Vec<Iter>);
+///     variants = [Number(u32), Stop(Stop)];
+/// );
+///
+/// let mut items = Items::from_exact_iter([3u32]);
+/// items.extend([1u32].into_iter().collect::<Items>());
+/// items.extend_from([4u32]);
+/// items.extend_from_hinted(1, [2u32]);
+/// items.extend([Item::Number(0)]);
+/// items.sort();
+/// items.extend_sorted([5u32]);
+/// assert_eq!(items.as_slice(), [0, 1, 2, 3, 4, 5].map(Item::Number));
+/// assert_eq!(items.into_sorted_by_key(|Item::Number(number)| 5 - number).first(), Some(&Item::Number(5)));
+///
+/// let mut iters = Iters::default();
+/// assert!(iters.extend_until_variant([Iter::Number(1), Iter::Stop(Stop), Iter::Number(2)], ItersVariantKind::Stop));
+/// assert_eq!(iters.find_map_number(|number| Some(*number)), Some(1));
+///
+/// #[derive(Debug)]
+/// pub struct Sink;
+///
+/// #[derive(From, Debug)]
+/// pub enum Inner {
+///     Number(u32),
+///     Stop(Stop),
+///     Sink(Sink),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Inners(Vec<Inner>);
+///     variants = [Number(u32), Stop(Stop), Sink(Sink)];
+/// );
+///
+/// let mut inners = Inners::default();
+/// inners.push(1u32);
+/// inners.push(Stop);
+/// inners.push(Sink);
+/// assert_eq!(inners.clear_stop(), 1);
+/// let mut sinks = Vec::new();
+/// assert_eq!(inners.drain_sink_into(&mut sinks), 1);
+/// assert!(inners.is_only_number());
+/// ```
+#[macro_export]
+macro_rules! define {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident(Vec<$inner:ty>)
+        $(where [$($where_clause:tt)*])?;
+        $(variants = [$($variants:tt)+];)?
+        $(variant_kind_derives = [$($variant_kind_derive:path),* $(,)?];)?
+        $(metric = $metric:expr;)?
+        $(serde_flatten_as = $serde_field:literal;)?
+        $(ring_buffer = $ring_buffer:expr;)?
+        $(compat_eq = [$($compat_eq:ty),+ $(,)?];)?
+        $(paired $paired:tt)?
+        $(debug_max = $debug_max:expr;)?
+        $(vec_from_variants $vec_from_variants:tt)?
+        $(warn_on_drop $warn_on_drop:tt)?
+        $(impl_attrs = [$(#[$impl_attr:meta]),* $(,)?];)?
+        $(on_push = $on_push:expr;)?
+        $(ord_by = $ord_by:ident;)?
+        $(serde_rename = $serde_rename:literal;)?
+        $(into = $into_target:ty => $into:expr;)?
+        $(serialize_grouped $serialize_grouped:tt)?
+        $(default_valid = $default_valid:expr;)?
+    ) => {
+        $crate::define_struct!(
+            $(#[$meta])*
+            $vis struct $name(Vec<$inner>)
+            $(where [$($where_clause)*])?;
+        );
+        $crate::define_into_iter!($vis, $name, $inner);
+        $crate::define_variant_kind!($vis, $name, [$($($variants)+)?] $(, derives = [$($variant_kind_derive),*])?);
+        $crate::with_impl_attrs!([$($(#[$impl_attr])*)?]
+        $crate::impl_self!($name, $inner $(, metric = $metric)? $(, ring_buffer = $ring_buffer)? $(, on_push = $on_push)?);
+        $crate::impl_default!($name);
+        $crate::impl_extend!($name, $inner $(, metric = $metric)? $(, ring_buffer = $ring_buffer)? $(, on_push = $on_push)?);
+        $crate::impl_from_iter!($name, $inner);
+        $crate::impl_into_iter_own!(@impls $name, [], [], $inner);
+        $crate::impl_into_iter_ref!($name, $inner);
+        $crate::impl_into_iter_mut!($name, $inner);
+        $crate::impl_deref!($name, $inner);
+        $crate::impl_deref_mut!($name, $inner);
+        $crate::impl_from_vec!($name, $inner $(, metric = $metric)? $(, on_push = $on_push)?);
+        $crate::impl_into_vec!($name, $inner);
+        $crate::impl_vec_deque!($name, $inner);
+        $crate::impl_chunks!($name, $inner);
+        $crate::impl_push_fmt!($name, $inner);
+        $crate::impl_extend_vec!($name, $inner);
+        $crate::impl_extract_first!($name, $inner);
+        $crate::impl_batches!($name, $inner);
+        $crate::impl_sort_dedup!($name, $inner);
+        $crate::impl_replace_with!($name, $inner);
+        $crate::impl_write_json_lines!($name, $inner);
+        $crate::impl_take_while!($name, $inner);
+        $crate::impl_snapshot!($name, $inner);
+        $crate::impl_into_single!($name, $inner);
+        $crate::impl_try_get!($name, $inner);
+        $crate::impl_par_retain!($name, $inner);
+        $crate::impl_deserialize_extend!($name, $inner);
+        $crate::impl_iter_peekable!($name, $inner);
+        $crate::impl_concat_all!($name, $inner);
+        $crate::impl_clone_into!($name, $inner);
+        $crate::impl_into_result!($name, $inner);
+        $crate::impl_dedup_global_by_key!($name, $inner);
+        $crate::impl_dedup_global_by_key_ord!($name, $inner);
+        $crate::impl_into_vec_compact!($name, $inner);
+        $crate::impl_ffi_parts!($name, $inner);
+        $crate::impl_partial_eq_vec!($name, $inner);
+        $crate::impl_as_slice!($name, $inner);
+        $crate::impl_into_sorted!($name, $inner);
+        $crate::impl_find_duplicates!($name, $inner);
+        $crate::impl_find_duplicates_ord!($name, $inner);
+        $crate::impl_variants!($vis, $name, $inner, [$($($variants)+)?] $(, variant_kind_derives = [$($variant_kind_derive),*])? $(, vec_from_variants $vec_from_variants)? $(, serialize_grouped $serialize_grouped)?);
+        $crate::impl_serde_options!($name, $inner, [$($serde_field)?], [$($serde_rename)?]);
+        $($crate::impl_compat_eq!($name, $inner, [$($compat_eq),+]);)?
+        $($crate::impl_paired!($name, $inner) $paired)?
+        $($crate::impl_debug_max!($name, $inner, $debug_max);)?
+        $($crate::impl_warn_on_drop!($name, $inner) $warn_on_drop)?
+        $($crate::impl_ord_by!($name, $inner, $ord_by);)?
+        $($crate::impl_into_target!($name, $inner, $into_target, $into);)?
+        );
+        $($crate::impl_default_valid!($name, $default_valid);)?
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident<$($param:ident),+ $(,)?>(Vec<$inner:ty>)
+        $(where [$($where_clause:tt)*])?;
+        $(variants = $variants:tt;)?
+        $(impl_attrs = [$(#[$impl_attr:meta]),* $(,)?];)?
+        $(default_valid = $default_valid:expr;)?
+    ) => {
+        $(compile_error!(concat!("the `variants = ", stringify!($variants), "` option can't be used with a generic wrapper"));)?
+        $crate::define_struct!(
+            $(#[$meta])*
+            $vis struct $name<$($param),+>(Vec<$inner>)
+            $(where [$($where_clause)*])?;
+        );
+        $crate::define_into_iter!($vis, $name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::with_impl_attrs!([$($(#[$impl_attr])*)?]
+        $crate::impl_self!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_default!($name, [$($param),+], [$($($where_clause)*)?]);
+        $crate::impl_extend!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_from_iter!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_into_iter_own!(@impls $name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_into_iter_ref!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_into_iter_mut!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_deref!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_deref_mut!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_from_vec!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_into_vec!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_vec_deque!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        $crate::impl_as_slice!($name, [$($param),+], [$($($where_clause)*)?], $inner);
+        );
+        $($crate::impl_default_valid!($name, $default_valid);)?
+    };
+}
+
+/// Applies the `impl_attrs = [...]` option of [`define!`] to the generated impls.
+///
+/// Attributes on a macro invocation are ignored, so the impls are wrapped in an anonymous `const` item that carries the attributes. The wrapper is omitted when there are no attributes.
+#[macro_export]
+macro_rules! with_impl_attrs {
+    ([] $($item:tt)*) => {
+        $($item)*
+    };
+    ([$(#[$attr:meta])+] $($item:tt)*) => {
+        $(#[$attr])+
+        const _: () = {
+            $($item)*
+        };
+    };
+}
+
+/// Dispatches the `variants = [...]` option of [`define!`]: the named form (`Kind(Payload)`) generates the variant-aware items, the type-only form generates the conversions only.
+///
+/// The `{Name}VariantKind` enum itself is generated by [`define_variant_kind!`].
+#[macro_export]
+macro_rules! impl_variants {
+    ($vis:vis, $name:ident, $inner:ty, [] $(, variant_kind_derives = $derives:tt)? $(, vec_from_variants $vec_from_variants:tt)? $(, serialize_grouped $serialize_grouped:tt)?) => {
+        $(compile_error!(concat!("the `variant_kind_derives = ", stringify!($derives), "` option requires named variants (`variants = [Kind(Payload), ...]`)"));)?
+        $(compile_error!(concat!("the `vec_from_variants", stringify!($vec_from_variants), "` option requires the `variants = [...]` option"));)?
+        $(compile_error!(concat!("the `serialize_grouped", stringify!($serialize_grouped), "` option requires named variants (`variants = [Kind(Payload), ...]`)"));)?
+    };
+    ($vis:vis, $name:ident, $inner:ty, [$($kind:ident($payload:ty)),+ $(,)?] $(, variant_kind_derives = [$($derive:path),*])? $(, vec_from_variants $vec_from_variants:tt)? $(, serialize_grouped $serialize_grouped:tt)?) => {
+        $crate::impl_from_value!($name, [$($payload),+]);
+        $crate::impl_from_value_for_vec!($inner, [$($payload),+] $(, $vec_from_variants)?);
+        $crate::impl_variant_kind!(@impls $name, $inner, [$($kind($payload)),+]);
+        $crate::impl_iter_tagged!($name, $inner);
+        $crate::impl_dedup_by_variant_keys!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_dedup_by_variant_keys_ord!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_into_vec_tuple!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_clear_variant!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_cap_total_keeping!($name, $inner);
+        $crate::impl_is_only_variant!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_extend_until_variant!($name, $inner);
+        $crate::impl_merge_duplicate_variant!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_find_map_variant!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_peek_is_variant!($name, $inner);
+        $crate::impl_retain_map_variant!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_variant_bitset!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_iter_variant_mut!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_drain_variant_into!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_variant_matching!($name, $inner);
+        $crate::impl_retain_frequent_variants!($name, $inner, [$($kind($payload)),+]);
+        $crate::impl_serialize_grouped!($name, $inner, [$($kind($payload)),+] $(, $serialize_grouped)?);
+    };
+    ($vis:vis, $name:ident, $inner:ty, [$($variant:ty),+ $(,)?] $(, variant_kind_derives = $derives:tt)? $(, vec_from_variants $vec_from_variants:tt)? $(, serialize_grouped $serialize_grouped:tt)?) => {
+        $crate::impl_from_value!($name, [$($variant),+]);
+        $crate::impl_from_value_for_vec!($inner, [$($variant),+] $(, $vec_from_variants)?);
+        $(compile_error!(concat!("the `variant_kind_derives = ", stringify!($derives), "` option requires named variants (`variants = [Kind(Payload), ...]`)"));)?
+        $(compile_error!(concat!("the `serialize_grouped", stringify!($serialize_grouped), "` option requires named variants (`variants = [Kind(Payload), ...]`)"));)?
+    };
+}
+
+/// Generates the struct, forwarding the attributes, including doc comments.
+///
+/// The generated methods are documented as well, so a crate that denies `missing_docs` can document the struct with a regular doc comment:
+///
+/// ```rust
+/// #![deny(missing_docs)]
+/// #![doc = "A crate that denies missing docs"]
+///
+/// use derive_more::From;
+///
+/// /// A single validation error
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     /// The value is empty
+///     Empty(EmptyError),
+///     /// The value is too long
+///     TooLong(TooLongError),
+/// }
+///
+/// /// The value is empty
+/// #[derive(Debug)]
+/// pub struct EmptyError;
+///
+/// /// The value is too long
+/// #[derive(Debug)]
+/// pub struct TooLongError;
+///
+/// vec_of_enum::define!(
+///     /// A collection of validation errors
+///     #[derive(Debug)]
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [Empty(EmptyError), TooLong(TooLongError)];
+/// );
+///
+/// fn main() {
+///     let errors = ValidationErrors::new(vec![EmptyError.into()]);
+///     assert!(errors.is_only_empty());
+/// }
+/// ```
+// `fn main` keeps the items at the crate root of the doctest, where `missing_docs` checks them
+#[allow(clippy::needless_doctest_main)]
+#[macro_export]
+macro_rules! define_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident(Vec<$inner:ty>)
+        $(where [$($where_clause:tt)*])?;
+    ) => {
+        #[repr(transparent)]
+        $(#[$meta])*
+        $vis struct $name($crate::__private::Vec<$inner>)
+        $(where $($where_clause)*)?;
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident<$($param:ident),+ $(,)?>(Vec<$inner:ty>)
+        $(where [$($where_clause:tt)*])?;
+    ) => {
+        #[repr(transparent)]
+        $(#[$meta])*
+        $vis struct $name<$($param),+>($crate::__private::Vec<$inner>)
+        $(where $($where_clause)*)?;
+    };
+}
+
+/// Generates `new`, `from_exact_iter`, `push`, `extend_from`, `extend_from_hinted` and `extend_sorted`.
+///
+/// `extend_from` maps the source iterator through `Into`, which preserves its `size_hint`, so extending from an iterator with an exact size hint reserves the capacity once:
+///
+/// ```rust
+/// use std::alloc::{GlobalAlloc, Layout, System};
+/// use std::collections::BTreeSet;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// struct CountingAllocator;
+///
+/// static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+///
+/// unsafe impl GlobalAlloc for CountingAllocator {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+///         unsafe { System.alloc(layout) }
+///     }
+///
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         unsafe { System.dealloc(ptr, layout) }
+///     }
+/// }
+///
+/// #[global_allocator]
+/// static GLOBAL: CountingAllocator = CountingAllocator;
+///
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u64>);
+/// );
+///
+/// fn main() {
+///     let mut numbers = Numbers::default();
+///     let before = ALLOCATIONS.load(Ordering::Relaxed);
+///     numbers.extend_from(0..1000u32);
+///     let after = ALLOCATIONS.load(Ordering::Relaxed);
+///     assert_eq!(after.checked_sub(before), Some(1));
+///     assert_eq!(numbers.len(), 1000);
+///
+///     // `BTreeSet` iterators report an exact size hint without implementing `TrustedLen`
+///     let set = (0..1000u32).collect::<BTreeSet<_>>();
+///     let mut numbers = Numbers::default();
+///     let before = ALLOCATIONS.load(Ordering::Relaxed);
+///     numbers.extend_from(set);
+///     let after = ALLOCATIONS.load(Ordering::Relaxed);
+///     assert_eq!(after.checked_sub(before), Some(1));
+///     assert_eq!(numbers.len(), 1000);
+///
+///     // `filter` reports no lower bound, so an explicit hint reserves the capacity instead
+///     let mut numbers = Numbers::default();
+///     let before = ALLOCATIONS.load(Ordering::Relaxed);
+///     numbers.extend_from_hinted(500, (0..1000u32).filter(|number| number % 2 == 0));
+///     let after = ALLOCATIONS.load(Ordering::Relaxed);
+///     assert_eq!(after.checked_sub(before), Some(1));
+///     assert_eq!(numbers.len(), 500);
+///
+///     // `from_exact_iter` allocates the exact length once
+///     let before = ALLOCATIONS.load(Ordering::Relaxed);
+///     let numbers = Numbers::from_exact_iter([1u32, 2, 3].iter().copied());
+///     let after = ALLOCATIONS.load(Ordering::Relaxed);
+///     assert_eq!(after.checked_sub(before), Some(1));
+///     assert_eq!(numbers.capacity(), 3);
+///
+///     // `FromIterator` preallocates from the size hint too (see [`impl_from_iter!`])
+///     let before = ALLOCATIONS.load(Ordering::Relaxed);
+///     let numbers = (0..1000u32).collect::<Numbers>();
+///     let after = ALLOCATIONS.load(Ordering::Relaxed);
+///     assert_eq!(after.checked_sub(before), Some(1));
+///     assert!(numbers.capacity() >= 1000);
+/// }
+/// ```
+///
+/// `extend_sorted` keeps a sorted collection sorted:
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let mut numbers = Numbers::new(vec![1, 3, 5, 7]);
+/// numbers.extend_sorted([6, 0, 3u8]);
+/// assert_eq!(numbers.as_slice(), [0, 1, 3, 3, 5, 6, 7]);
+/// ```
+#[macro_export]
+macro_rules! impl_self {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty $(, metric = $metric:expr)? $(, ring_buffer = $ring_buffer:expr)? $(, on_push = $on_push:expr)?) => {
+        impl<$($param),*> $name<$($param),*>
+        where $($where_clause)*
+        {
+            /// Creates a new collection from anything that converts into the underlying `Vec`.
+            ///
+            /// Calls the `on_push` hook (if any) for every element.
+            pub fn new(inner: impl Into<$crate::__private::Vec<$inner>>) -> Self {
+                // The generated methods that re-wrap the elements of an existing wrapper use `Self(vec)` instead, so the hook isn't called twice
+                Self::from(inner.into())
+            }
+
+            /// Creates a new collection from an iterator of known length, allocating the exact capacity once.
+            pub fn from_exact_iter(iter: impl IntoIterator<Item = impl Into<$inner>, IntoIter: ExactSizeIterator>) -> Self {
+                let iter = iter.into_iter();
+                let capacity = iter.len();
+                $(let capacity = capacity.min($ring_buffer);)?
+                let mut wrapper = Self($crate::__private::Vec::with_capacity(capacity));
+                wrapper.extend(iter.map(Into::<$inner>::into));
+                wrapper
+            }
+
+            /// Appends a value that converts into the element type.
+            pub fn push(&mut self, value: impl Into<$inner>) {
+                $($crate::metric_counter!($metric).increment(1);)?
+                let value = value.into();
+                $($crate::on_push!($on_push, $inner)(&value);)?
+                self.0.push(value);
+                $($crate::evict_oldest!(self.0, $ring_buffer);)?
+            }
+
+            /// Appends every value of the iterator, converting each into the element type.
+            pub fn extend_from(&mut self, iter: impl IntoIterator<Item = impl Into<$inner>>) {
+                self.extend(iter.into_iter().map(Into::<$inner>::into))
+            }
+
+            /// Reserves the capacity for `hint` more elements, then appends every value of the iterator like [`Self::extend_from`]. Useful for the iterators that can't report their length (e.g. a `filter` chain).
+            pub fn extend_from_hinted(&mut self, hint: usize, iter: impl IntoIterator<Item = impl Into<$inner>>) {
+                self.0.reserve(hint);
+                self.extend_from(iter)
+            }
+
+            /// Inserts every value of the iterator at its sorted position (after the equal elements), keeping a sorted collection sorted. Requires the element type to implement `Ord`.
+            ///
+            /// The collection must already be sorted. Every insertion shifts the following elements, so this is efficient for adding a few elements to a large collection; sort once after `extend_from` to add many. With the `ring_buffer` option, the excess elements are evicted from the front (the smallest ones).
+            pub fn extend_sorted(&mut self, iter: impl IntoIterator<Item = impl Into<$inner>>)
+            where
+                // The `for<'a>` bound doesn't use the lifetime, but it makes the bound non-trivial, so the compiler checks it where the method or the impl is used instead of rejecting the generated code when the element type doesn't implement the trait
+                for<'a> $inner: Ord,
+            {
+                iter.into_iter().map(Into::<$inner>::into).for_each(|value| {
+                    $($crate::metric_counter!($metric).increment(1);)?
+                    $($crate::on_push!($on_push, $inner)(&value);)?
+                    let index = self.0.partition_point(|element| element <= &value);
+                    self.0.insert(index, value);
+                    $($crate::evict_oldest_amortized!(self.0, $ring_buffer);)?
+                });
+                $($crate::evict_oldest!(self.0, $ring_buffer);)?
+            }
+        }
+    };
+    ($name:ident, $inner:ty $(, metric = $metric:expr)? $(, ring_buffer = $ring_buffer:expr)? $(, on_push = $on_push:expr)?) => {
+        $crate::impl_self!($name, [], [], $inner $(, metric = $metric)? $(, ring_buffer = $ring_buffer)? $(, on_push = $on_push)?);
+    };
+}
+
+/// Generates `Default`, which returns an empty collection.
+///
+/// `#[serde(default)]` on a field of the wrapper type relies on this impl, so a missing field deserializes into an empty collection:
+///
+/// ```rust
+/// use serde::Deserialize;
+///
+/// vec_of_enum::define!(
+///     #[derive(Deserialize, Debug)]
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// #[derive(Deserialize, Debug)]
+/// pub struct Report {
+///     #[serde(default)]
+///     messages: Messages,
+/// }
+///
+/// let report: Report = serde_json::from_str("{}")?;
+/// assert!(report.messages.is_empty());
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[macro_export]
+macro_rules! impl_default {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*]) => {
+        impl<$($param),*> Default for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn default() -> Self {
+                Self(Default::default())
+            }
+        }
+    };
+    ($name:ident) => {
+        $crate::impl_default!($name, [], []);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_extend {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty $(, metric = $metric:expr)?, ring_buffer = $ring_buffer:expr $(, on_push = $on_push:expr)?) => {
+        impl<$($param),*> Extend<$inner> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn extend<__VecOfEnumIter: IntoIterator<Item = $inner>>(&mut self, iter: __VecOfEnumIter) {
+                iter.into_iter()$(.inspect($crate::metric_increment!($metric)))?.for_each(|value| {
+                    $($crate::on_push!($on_push, $inner)(&value);)?
+                    self.0.push(value);
+                    $crate::evict_oldest_amortized!(self.0, $ring_buffer);
+                });
+                $crate::evict_oldest!(self.0, $ring_buffer);
+            }
+        }
+    };
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty $(, metric = $metric:expr)? $(, on_push = $on_push:expr)?) => {
+        impl<$($param),*> Extend<$inner> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn extend<__VecOfEnumIter: IntoIterator<Item = $inner>>(&mut self, iter: __VecOfEnumIter) {
+                self.0.extend(iter.into_iter()$(.inspect($crate::metric_increment!($metric)))?$(.inspect($crate::on_push!($on_push, $inner)))?);
+            }
+        }
+    };
+    ($name:ident, $inner:ty $(, metric = $metric:expr)? $(, ring_buffer = $ring_buffer:expr)? $(, on_push = $on_push:expr)?) => {
+        $crate::impl_extend!($name, [], [], $inner $(, metric = $metric)? $(, ring_buffer = $ring_buffer)? $(, on_push = $on_push)?);
+    };
+}
+
+/// Generates the `{Name}IntoIter` owning iterator and `impl IntoIterator for $name`.
+///
+/// The iterator is a newtype over `vec::IntoIter`, so the public API doesn't depend on the backing store. It implements `DoubleEndedIterator`, `ExactSizeIterator` and `FusedIterator`, and, like `vec::IntoIter`, `Debug` and `Clone` (if the element type does) and `as_slice`.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let iter: MessagesIntoIter = Messages::new(vec!["a", "b"]).into_iter();
+/// assert_eq!(iter.collect::<Vec<_>>(), ["a", "b"]);
+///
+/// let mut iter = Messages::new(vec!["a", "b", "c"]).into_iter();
+/// assert_eq!(iter.len(), 3);
+/// assert_eq!(iter.next_back(), Some("c"));
+/// assert_eq!(iter.len(), 2);
+/// assert_eq!(iter.by_ref().rev().collect::<Vec<_>>(), ["b", "a"]);
+/// // The iterator is fused, so it keeps returning `None` after the end
+/// fn is_fused(_: &impl std::iter::FusedIterator) -> bool {
+///     true
+/// }
+/// assert!(is_fused(&iter));
+/// assert_eq!(iter.next(), None);
+/// assert_eq!(iter.next(), None);
+///
+/// let mut iter = Messages::new(vec!["a", "b"]).into_iter();
+/// assert_eq!(iter.next(), Some("a"));
+/// assert_eq!(iter.as_slice(), ["b"]);
+/// assert_eq!(format!("{iter:?}"), r#"MessagesIntoIter(["b"])"#);
+/// assert_eq!(iter.clone().collect::<Vec<_>>(), ["b"]);
+/// assert_eq!(iter.len(), 1);
+/// ```
+#[macro_export]
+macro_rules! impl_into_iter_own {
+    (@impls $name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        $crate::__private::paste! {
+            impl<$($param),*> Iterator for [<$name IntoIter>]<$($param),*>
+            where $($where_clause)*
+            {
+                type Item = $inner;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    self.0.next()
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.0.size_hint()
+                }
+            }
+
+            impl<$($param),*> DoubleEndedIterator for [<$name IntoIter>]<$($param),*>
+            where $($where_clause)*
+            {
+                fn next_back(&mut self) -> Option<Self::Item> {
+                    self.0.next_back()
+                }
+            }
+
+            impl<$($param),*> ExactSizeIterator for [<$name IntoIter>]<$($param),*> where $($where_clause)* {}
+
+            impl<$($param),*> ::core::iter::FusedIterator for [<$name IntoIter>]<$($param),*> where $($where_clause)* {}
+
+            impl<$($param),*> [<$name IntoIter>]<$($param),*>
+            where $($where_clause)*
+            {
+                /// Returns the remaining elements as a slice.
+                pub fn as_slice(&self) -> &[$inner] {
+                    self.0.as_slice()
+                }
+            }
+
+            impl<$($param),*> ::core::fmt::Debug for [<$name IntoIter>]<$($param),*>
+            where
+                for<'a> $inner: ::core::fmt::Debug,
+                $($where_clause)*
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_tuple(stringify!([<$name IntoIter>])).field(&self.0.as_slice()).finish()
+                }
+            }
+
+            impl<$($param),*> Clone for [<$name IntoIter>]<$($param),*>
+            where
+                for<'a> $inner: Clone,
+                $($where_clause)*
+            {
+                fn clone(&self) -> Self {
+                    Self(self.0.clone())
+                }
+            }
+
+            impl<$($param),*> IntoIterator for $name<$($param),*>
+            where $($where_clause)*
+            {
+                type Item = $inner;
+                type IntoIter = [<$name IntoIter>]<$($param),*>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    [<$name IntoIter>]($crate::__private::Vec::from(self).into_iter())
+                }
+            }
+        }
+    };
+    ($vis:vis, $name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        $crate::define_into_iter!($vis, $name, [$($param),*], [$($where_clause)*], $inner);
+        $crate::impl_into_iter_own!(@impls $name, [$($param),*], [$($where_clause)*], $inner);
+    };
+    ($vis:vis, $name:ident, $inner:ty) => {
+        $crate::impl_into_iter_own!($vis, $name, [], [], $inner);
+    };
+}
+
+/// Generates the `{Name}IntoIter` struct without its impls (see [`impl_into_iter_own!`]).
+#[macro_export]
+macro_rules! define_into_iter {
+    ($vis:vis, $name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        $crate::__private::paste! {
+            #[doc = concat!("An owning iterator over the elements of [`", stringify!($name), "`].")]
+            $vis struct [<$name IntoIter>]<$($param),*>($crate::__private::vec::IntoIter<$inner>)
+            where $($where_clause)*;
+        }
+    };
+    ($vis:vis, $name:ident, $inner:ty) => {
+        $crate::define_into_iter!($vis, $name, [], [], $inner);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_into_iter_ref {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<'a, $($param),*> IntoIterator for &'a $name<$($param),*>
+        where $($where_clause)*
+        {
+            type Item = &'a $inner;
+            type IntoIter = ::core::slice::Iter<'a, $inner>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_into_iter_ref!($name, [], [], $inner);
+    };
+}
+
+/// Generates `impl IntoIterator for &mut $name`.
+///
+/// The reference iterators are the slice iterators, which implement `DoubleEndedIterator`, `ExactSizeIterator` and `FusedIterator`.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let mut numbers = Numbers::new(vec![1, 2, 3]);
+/// for number in &mut numbers {
+///     *number *= 10;
+/// }
+/// assert_eq!((&mut numbers).into_iter().len(), 3);
+/// assert_eq!((&numbers).into_iter().rev().collect::<Vec<_>>(), [&30, &20, &10]);
+/// ```
+#[macro_export]
+macro_rules! impl_into_iter_mut {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<'a, $($param),*> IntoIterator for &'a mut $name<$($param),*>
+        where $($where_clause)*
+        {
+            type Item = &'a mut $inner;
+            type IntoIter = ::core::slice::IterMut<'a, $inner>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter_mut()
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_into_iter_mut!($name, [], [], $inner);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_deref {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<$($param),*> ::core::ops::Deref for $name<$($param),*>
+        where $($where_clause)*
+        {
+            type Target = $crate::__private::Vec<$inner>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_deref!($name, [], [], $inner);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_deref_mut {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<$($param),*> ::core::ops::DerefMut for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_deref_mut!($name, [], [], $inner);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_from_vec {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty $(, metric = $metric:expr)? $(, on_push = $on_push:expr)?) => {
+        impl<$($param),*> From<$crate::__private::Vec<$inner>> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn from(vec: $crate::__private::Vec<$inner>) -> Self {
+                // A `usize` length always fits into `u64` on the supported platforms, so the fallback is never used
+                $($crate::metric_counter!($metric).increment(u64::try_from(vec.len()).unwrap_or(u64::MAX));)?
+                $(vec.iter().for_each($crate::on_push!($on_push, $inner));)?
+                Self(vec)
+            }
+        }
+    };
+    ($name:ident, $inner:ty $(, metric = $metric:expr)? $(, on_push = $on_push:expr)?) => {
+        $crate::impl_from_vec!($name, [], [], $inner $(, metric = $metric)? $(, on_push = $on_push)?);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_from_value {
+    ($name:ident, [$($value_source:ty),+]) => {
+        $(
+            $crate::impl_from_value!($name, $value_source);
+        )+
+    };
+    ($name:ident, $value_source:ty) => {
+        impl From<$value_source> for $name {
+            fn from(source: $value_source) -> Self {
+                let mut wrapper = Self($crate::__private::Vec::with_capacity(1));
+                wrapper.push(source);
+                wrapper
+            }
+        }
+    };
+}
+
+/// Generates `impl From<$value_source> for Vec<$inner>` for every variant type when the `vec_from_variants;` option is set.
+#[macro_export]
+macro_rules! impl_from_value_for_vec {
+    ($inner:ty, [$($value_source:ty),+]) => {};
+    ($inner:ty, [$($value_source:ty),+], ;) => {
+        $(
+            impl From<$value_source> for $crate::__private::Vec<$inner> {
+                fn from(source: $value_source) -> Self {
+                    $crate::__private::vec![source.into()]
+                }
+            }
+        )+
+    };
+}
+
+/// Generates `impl From<$name> for Vec<$inner>`.
+///
+/// Both `From<Vec<$inner>>` and this conversion move the vector without reallocation, so a round trip returns the same allocation (same pointer, length and capacity).
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let mut vec = Vec::with_capacity(16);
+/// vec.push(String::from("hello"));
+/// let (ptr, capacity) = (vec.as_ptr(), vec.capacity());
+///
+/// let messages = Messages::from(vec);
+/// assert_eq!(messages.as_ptr(), ptr);
+///
+/// let vec: Vec<String> = messages.into();
+/// assert_eq!(vec.as_ptr(), ptr);
+/// assert_eq!(vec.capacity(), capacity);
+/// assert_eq!(vec, ["hello"]);
+/// ```
+#[macro_export]
+macro_rules! impl_into_vec {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<$($param),*> From<$name<$($param),*>> for $crate::__private::Vec<$inner>
+        where $($where_clause)*
+        {
+            fn from(mut value: $name<$($param),*>) -> Self {
+                // Taking the elements instead of moving the field keeps the conversion valid when the wrapper implements `Drop` (see the `warn_on_drop` option)
+                ::core::mem::take(&mut value.0)
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_into_vec!($name, [], [], $inner);
+    };
+}
+
+/// Generates the `{Name}VariantKind` tag enum and `impl From<&$inner>` for it.
+///
+/// The derives default to `Debug, Clone, Copy, PartialEq, Eq, Hash`. The generated methods don't rely on any of them, so the list may be replaced with any other list.
+///
+/// ```rust
+/// # use derive_more::From;
+/// # use std::collections::BTreeSet;
+/// #
+/// # pub struct FatalError;
+/// #
+/// # pub struct WarningError;
+/// #
+/// #[derive(From)]
+/// pub enum Problem {
+///     Fatal(FatalError),
+///     Warning(WarningError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError)];
+///     variant_kind_derives = [Debug, PartialEq, Eq, PartialOrd, Ord];
+/// );
+///
+/// let mut problems = Problems::default();
+/// problems.push(WarningError);
+/// problems.push(FatalError);
+/// problems.push(WarningError);
+///
+/// let kinds = problems.iter_tagged().map(|(kind, _)| kind).collect::<BTreeSet<_>>();
+/// assert_eq!(problems.iter_tagged().len(), 3);
+/// assert_eq!(problems.iter_tagged().rev().map(|(kind, _)| kind).next(), Some(ProblemsVariantKind::Warning));
+/// assert_eq!(kinds.into_iter().collect::<Vec<_>>(), [ProblemsVariantKind::Fatal, ProblemsVariantKind::Warning]);
+///
+/// vec_of_enum::define!(
+///     pub struct BareProblems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError)];
+///     variant_kind_derives = [];
+/// );
+///
+/// assert!(!BareProblems::new(problems).is_only_variant(BareProblemsVariantKind::Warning));
+/// ```
+#[macro_export]
+macro_rules! impl_variant_kind {
+    (@impls $name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl<'a> From<&'a $inner> for [<$name VariantKind>] {
+                fn from(value: &'a $inner) -> Self {
+                    type __VecOfEnumInner = $inner;
+                    match value {
+                        $(__VecOfEnumInner::$kind(_) => Self::$kind),+
+                    }
+                }
+            }
+        }
+    };
+    ($vis:vis, $name:ident, $inner:ty, [$($kind:ident($payload:ty)),+] $(, derives = [$($derive:path),*])?) => {
+        $crate::define_variant_kind!($vis, $name, [$($kind($payload)),+] $(, derives = [$($derive),*])?);
+        $crate::impl_variant_kind!(@impls $name, $inner, [$($kind($payload)),+]);
+    };
+}
+
+/// Generates the `{Name}VariantKind` enum without its impls (see [`impl_variant_kind!`]).
+///
+/// Accepts the `variants = [...]` list as is: the type-only form generates nothing.
+#[macro_export]
+macro_rules! define_variant_kind {
+    ($vis:vis, $name:ident, [] $(, derives = $derives:tt)?) => {};
+    ($vis:vis, $name:ident, [$($kind:ident($payload:ty)),+ $(,)?]) => {
+        $crate::define_variant_kind!($vis, $name, [$($kind($payload)),+], derives = [Debug, Clone, Copy, PartialEq, Eq, Hash]);
+    };
+    ($vis:vis, $name:ident, [$($kind:ident($payload:ty)),+ $(,)?], derives = [$($derive:path),*]) => {
+        $crate::__private::paste! {
+            #[doc = concat!("The variant kinds of [`", stringify!($name), "`] elements.")]
+            #[derive($($derive),*)]
+            $vis enum [<$name VariantKind>] {
+                $(
+                    #[doc = concat!("The `", stringify!($kind), "` variant.")]
+                    $kind
+                ),+
+            }
+        }
+    };
+    ($vis:vis, $name:ident, [$($variant:ty),+ $(,)?] $(, derives = $derives:tt)?) => {};
+}
+
+/// Generates `iter_tagged`, which pairs every element with its variant kind.
+#[macro_export]
+macro_rules! impl_iter_tagged {
+    ($name:ident, $inner:ty) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Returns an iterator over the elements paired with their variant kinds.
+                pub fn iter_tagged(&self) -> impl DoubleEndedIterator<Item = ([<$name VariantKind>], &$inner)> + ExactSizeIterator + ::core::iter::FusedIterator {
+                    self.0.iter().map(|value| (value.into(), value))
+                }
+            }
+        }
+    };
+}
+
+/// Generates the `chunks_exact` and `rchunks` methods.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let numbers = Numbers::new(vec![1, 2, 3, 4, 5]);
+/// let size = 2;
+/// let mut chunks = numbers.chunks_exact(size);
+/// assert_eq!(chunks.by_ref().collect::<Vec<_>>(), [[1, 2], [3, 4]]);
+/// assert_eq!(chunks.remainder(), [5]);
+/// assert_eq!(numbers.rchunks(size).collect::<Vec<_>>(), [&[4, 5][..], &[2, 3], &[1]]);
+/// ```
+#[macro_export]
+macro_rules! impl_chunks {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns an iterator over chunks of exactly `size` elements, starting at the beginning.
+            ///
+            /// If `size` doesn't divide the length, the last up to `size - 1` elements are not yielded by the iterator; they can be retrieved with [`ChunksExact::remainder`](core::slice::ChunksExact::remainder).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `size` is zero.
+            pub fn chunks_exact(&self, size: usize) -> ::core::slice::ChunksExact<'_, $inner> {
+                self.0.chunks_exact(size)
+            }
+
+            /// Returns an iterator over chunks of `size` elements, starting at the end.
+            ///
+            /// If `size` doesn't divide the length, the last chunk (containing the first elements) is shorter than `size`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `size` is zero.
+            pub fn rchunks(&self, size: usize) -> ::core::slice::RChunks<'_, $inner> {
+                self.0.rchunks(size)
+            }
+        }
+    };
+}
+
+/// Generates `write_json_lines`, which writes the elements as newline-delimited JSON (requires the `serde_json` feature).
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let messages = Messages::new(vec!["hello".to_string(), "world".to_string()]);
+/// let mut output = Vec::new();
+/// messages.write_json_lines(&mut output)?;
+/// assert_eq!(output, b"\"hello\"\n\"world\"\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "serde_json")]
+#[macro_export]
+macro_rules! impl_write_json_lines {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Writes every element as JSON on its own line. Requires the element type to implement `Serialize`.
+            pub fn write_json_lines(&self, writer: &mut impl $crate::__private::io::Write) -> $crate::__private::io::Result<()>
+            where
+                for<'a> $inner: $crate::__private::serde::Serialize,
+            {
+                self.0.iter().try_for_each(|value| {
+                    $crate::__private::serde_json::to_writer(&mut *writer, value)?;
+                    writer.write_all(b"\n")
+                })
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde_json"))]
+#[macro_export]
+macro_rules! impl_write_json_lines {
+    ($name:ident, $inner:ty) => {};
+}
+
+/// Generates `PartialEq` impls (in both directions) between the wrapper and other wrappers over the same inner type.
+#[macro_export]
+macro_rules! impl_compat_eq {
+    ($name:ident, $inner:ty, [$($other:ty),+]) => {
+        $(
+            impl PartialEq<$other> for $name
+            where
+                for<'a> $inner: PartialEq,
+            {
+                fn eq(&self, other: &$other) -> bool {
+                    self.0 == **other
+                }
+            }
+
+            impl PartialEq<$name> for $other
+            where
+                for<'a> $inner: PartialEq,
+            {
+                fn eq(&self, other: &$name) -> bool {
+                    **self == other.0
+                }
+            }
+        )+
+    };
+}
+
+/// Generates `keys` and `errors_for` for wrappers over key-value pairs (the element type must implement [`Pair`]).
+#[macro_export]
+macro_rules! impl_paired {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns an iterator over the keys of the elements (in insertion order, including the duplicates).
+            pub fn keys(&self) -> impl DoubleEndedIterator<Item = &<$inner as $crate::Pair>::Key> + ExactSizeIterator + ::core::iter::FusedIterator {
+                self.0.iter().map($crate::Pair::key)
+            }
+
+            /// Returns an iterator over the values of the elements with the given key (in insertion order). Requires the key type to implement `PartialEq`.
+            pub fn errors_for<'a>(&'a self, key: &'a <$inner as $crate::Pair>::Key) -> impl DoubleEndedIterator<Item = &'a <$inner as $crate::Pair>::Value> + ::core::iter::FusedIterator
+            where
+                for<'b> <$inner as $crate::Pair>::Key: PartialEq,
+            {
+                self.0
+                    .iter()
+                    .filter(move |pair| $crate::Pair::key(*pair) == key)
+                    .map($crate::Pair::value)
+            }
+        }
+    };
+}
+
+/// Generates a `Debug` impl that prints at most `max` elements, followed by the number of the remaining elements.
+#[macro_export]
+macro_rules! impl_debug_max {
+    ($name:ident, $inner:ty, $max:expr) => {
+        impl ::core::fmt::Debug for $name
+        where
+            for<'a> $inner: ::core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&$crate::__private::DebugTruncated::new(&self.0, $max))
+                    .finish()
+            }
+        }
+    };
+}
+
+/// Dispatches the `serde_flatten_as` and `serde_rename` options of [`define!`].
+#[macro_export]
+macro_rules! impl_serde_options {
+    ($name:ident, $inner:ty, [], []) => {};
+    ($name:ident, $inner:ty, [$field:literal], [$($rename:literal)?]) => {
+        $crate::impl_serde_flatten_as!($name, $inner, $field $(, rename = $rename)?);
+    };
+    ($name:ident, $inner:ty, [], [$rename:literal]) => {
+        compile_error!(concat!("the `serde_rename = ", stringify!($rename), "` option requires the `serde_flatten_as` option (add `#[serde(rename = ", stringify!($rename), ")]` to the derived impls instead)"));
+    };
+}
+
+/// Generates `Serialize` and `Deserialize` impls that represent the wrapper as a struct with a single field (requires the `serde` feature).
+///
+/// The struct is named after the wrapper unless the name is overridden with `rename = "..."`.
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! impl_serde_flatten_as {
+    ($name:ident, $inner:ty, $field:literal) => {
+        $crate::impl_serde_flatten_as!($name, $inner, $field, rename = stringify!($name));
+    };
+    ($name:ident, $inner:ty, $field:literal, rename = $rename:expr) => {
+        impl $crate::__private::serde::Serialize for $name
+        where
+            for<'a> $inner: $crate::__private::serde::Serialize,
+        {
+            fn serialize<__VecOfEnumSerializer: $crate::__private::serde::Serializer>(&self, serializer: __VecOfEnumSerializer) -> Result<__VecOfEnumSerializer::Ok, __VecOfEnumSerializer::Error> {
+                $crate::__private::serialize_single_field(serializer, $rename, $field, &self.0)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name
+        where
+            for<'a> $inner: $crate::__private::serde::Deserialize<'de>,
+        {
+            fn deserialize<__VecOfEnumDeserializer: $crate::__private::serde::Deserializer<'de>>(deserializer: __VecOfEnumDeserializer) -> Result<Self, __VecOfEnumDeserializer::Error> {
+                $crate::__private::deserialize_single_field(deserializer, $rename, &[$field]).map(Self)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+macro_rules! impl_serde_flatten_as {
+    ($name:ident, $inner:ty, $field:literal $(, rename = $rename:expr)?) => {
+        compile_error!("the `serde_flatten_as` option requires the `serde` feature of `vec-of-enum`");
+    };
+}
+
+/// Expands to the `on_push` hook, coerced to `fn(&$inner)`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! on_push {
+    ($on_push:expr, $inner:ty) => {{
+        let on_push: fn(&$inner) = $on_push;
+        on_push
+    }};
+}
+
+/// PRUNING: Removes the oldest elements of the vector until its length is at most the capacity, because the `ring_buffer` option keeps only the most recent elements.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! evict_oldest {
+    ($vec:expr, $capacity:expr) => {{
+        // SAFETY: the number of excess elements is zero if the length doesn't exceed the capacity
+        let excess = $vec.len().saturating_sub($capacity);
+        $vec.drain(..excess);
+    }};
+}
+
+/// PRUNING: Removes the oldest elements of the vector once its length exceeds twice the capacity, because the `ring_buffer` option keeps only the most recent elements. The removal is batched, so a long iterator is evicted in batches: the vector never grows past twice the capacity, and every element is shifted at most once.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! evict_oldest_amortized {
+    ($vec:expr, $capacity:expr) => {{
+        // SAFETY: a capacity above `usize::MAX / 2` can't be exceeded twice, so the saturated limit is never reached
+        if $vec.len() > usize::saturating_mul($capacity, 2) {
+            $crate::evict_oldest!($vec, $capacity);
+        }
+    }};
+}
+
+/// Expands to a closure that increments the `metrics` counter with the given name once per call (requires the `metrics` feature).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! metric_increment {
+    ($metric:expr) => {{
+        let counter = $crate::metric_counter!($metric);
+        move |_: &_| counter.increment(1)
+    }};
+}
+
+/// Expands to the `metrics` counter with the given name (requires the `metrics` feature).
+#[cfg(feature = "metrics")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! metric_counter {
+    ($metric:expr) => {
+        $crate::__private::metrics::counter!($metric)
+    };
+}
+
+#[cfg(not(feature = "metrics"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! metric_counter {
+    ($metric:expr) => {
+        compile_error!("the `metric` option requires the `metrics` feature of `vec-of-enum`")
+    };
+}
+
+/// Generates `dedup_by_variant_keys`, which keeps only the first element per (variant, key) pair.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(Debug)]
+/// pub struct PasswordMinLengthError {
+///     min_length: usize,
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct InvalidEmailError {
+///     email: String,
+/// }
+///
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     PasswordMinLength(PasswordMinLengthError),
+///     InvalidEmail(InvalidEmailError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [PasswordMinLength(PasswordMinLengthError), InvalidEmail(InvalidEmailError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError { email: "a@example.com".into() });
+/// errors.push(PasswordMinLengthError { min_length: 8 });
+/// errors.push(InvalidEmailError { email: "b@example.com".into() });
+/// errors.push(InvalidEmailError { email: "a@example.com".into() });
+/// errors.push(PasswordMinLengthError { min_length: 8 });
+///
+/// let removed = errors.dedup_by_variant_keys(|error| error.min_length, |error| error.email.clone());
+/// assert_eq!(removed, 2);
+/// assert_eq!(errors.len(), 3);
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! impl_dedup_by_variant_keys {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Keeps only the first element for every distinct (variant, key) pair, where the key is computed by the closure for the element's variant. Returns the number of removed elements.
+                pub fn dedup_by_variant_keys<$([<__VecOfEnum $kind Key>]: Eq + ::core::hash::Hash),+>(&mut self, $(mut [<$kind:snake _key>]: impl FnMut(&$payload) -> [<__VecOfEnum $kind Key>]),+) -> usize {
+                    type __VecOfEnumInner = $inner;
+                    $(let mut [<$kind:snake _seen>] = $crate::__private::HashSet::new();)+
+                    let len = self.0.len();
+                    self.0.retain(|value| match value {
+                        $(__VecOfEnumInner::$kind(payload) => [<$kind:snake _seen>].insert([<$kind:snake _key>](payload)),)+
+                    });
+                    // SAFETY: `retain` never increases the length, so the subtraction never saturates
+                    len.saturating_sub(self.0.len())
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! impl_dedup_by_variant_keys {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {};
+}
+
+/// Generates `dedup_by_variant_keys_ord`, which keeps only the first element per (variant, key) pair, like `dedup_by_variant_keys`, but stores the seen keys in a `BTreeSet` (the keys must implement `Ord` instead of `Hash`).
+///
+/// Unlike `dedup_by_variant_keys`, the method doesn't require the `std` feature.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct PasswordMinLengthError {
+/// #     min_length: usize,
+/// # }
+/// #
+/// # #[derive(Debug)]
+/// # pub struct InvalidEmailError {
+/// #     email: String,
+/// # }
+/// #
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     PasswordMinLength(PasswordMinLengthError),
+///     InvalidEmail(InvalidEmailError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [PasswordMinLength(PasswordMinLengthError), InvalidEmail(InvalidEmailError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError { email: "a@example.com".into() });
+/// errors.push(PasswordMinLengthError { min_length: 8 });
+/// errors.push(InvalidEmailError { email: "a@example.com".into() });
+///
+/// let removed = errors.dedup_by_variant_keys_ord(|error| error.min_length, |error| error.email.clone());
+/// assert_eq!(removed, 1);
+/// ```
+#[macro_export]
+macro_rules! impl_dedup_by_variant_keys_ord {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Keeps only the first element for every distinct (variant, key) pair, where the key is computed by the closure for the element's variant. Returns the number of removed elements.
+                pub fn dedup_by_variant_keys_ord<$([<__VecOfEnum $kind Key>]: Ord),+>(&mut self, $(mut [<$kind:snake _key>]: impl FnMut(&$payload) -> [<__VecOfEnum $kind Key>]),+) -> usize {
+                    type __VecOfEnumInner = $inner;
+                    $(let mut [<$kind:snake _seen>] = $crate::__private::BTreeSet::new();)+
+                    let len = self.0.len();
+                    self.0.retain(|value| match value {
+                        $(__VecOfEnumInner::$kind(payload) => [<$kind:snake _seen>].insert([<$kind:snake _key>](payload)),)+
+                    });
+                    // SAFETY: `retain` never increases the length, so the subtraction never saturates
+                    len.saturating_sub(self.0.len())
+                }
+            }
+        }
+    };
+}
+
+/// Generates `push_fmt`, which pushes a formatted element.
+///
+/// The method can be called only if the element type implements `From<String>`; for other element types it exists but can't be called.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let field = "email";
+/// let mut messages = Messages::default();
+/// messages.push_fmt(format_args!("{field} is invalid"));
+/// assert_eq!(messages.as_slice(), ["email is invalid"]);
+/// ```
+#[macro_export]
+macro_rules! impl_push_fmt {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Formats the arguments into a `String` and pushes it. Requires the element type to implement `From<String>`.
+            pub fn push_fmt(&mut self, args: ::core::fmt::Arguments<'_>)
+            where
+                for<'a> $inner: From<$crate::__private::String>,
+            {
+                self.push($crate::__private::format(args))
+            }
+        }
+    };
+}
+
+/// Generates `extend_vec` and `impl Extend<$name> for Vec<$inner>` for merging wrappers into external `Vec`s.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let mut target = vec![String::from("a")];
+/// Messages::new(vec![String::from("b")]).extend_vec(&mut target);
+/// target.extend([Messages::new(vec![String::from("c")]), Messages::new(vec![String::from("d")])]);
+/// assert_eq!(target, ["a", "b", "c", "d"]);
+/// ```
+#[macro_export]
+macro_rules! impl_extend_vec {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Appends the elements to the end of `target`. This is the way to merge the wrapper into an external `Vec`.
+            pub fn extend_vec(self, target: &mut $crate::__private::Vec<$inner>) {
+                target.extend($crate::__private::Vec::from(self))
+            }
+        }
+
+        impl Extend<$name> for $crate::__private::Vec<$inner> {
+            fn extend<__VecOfEnumIter: IntoIterator<Item = $name>>(&mut self, iter: __VecOfEnumIter) {
+                iter.into_iter().for_each(|value| value.extend_vec(self))
+            }
+        }
+    };
+}
+
+/// Generates `impl<T: Into<$inner>> FromIterator<T> for $name`.
+///
+/// This blanket impl is the only generated `FromIterator` impl: it covers the inner type itself (via the reflexive `impl<T> From<T> for T`) and every type that converts into it, including the variant payload types.
+/// A second `FromIterator` impl for the wrapper would conflict with it:
+///
+/// ```compile_fail,E0119
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u64>);
+/// );
+///
+/// impl FromIterator<u64> for Numbers {
+///     fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+///         Self::new(iter.into_iter().collect::<Vec<_>>())
+///     }
+/// }
+/// ```
+///
+/// Collecting the inner type and a variant payload type:
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct PasswordMinLengthError;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct InvalidEmailError;
+/// #
+/// #[derive(From, Debug, PartialEq)]
+/// pub enum ValidationError {
+///     PasswordMinLength(PasswordMinLengthError),
+///     InvalidEmail(InvalidEmailError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [PasswordMinLength(PasswordMinLengthError), InvalidEmail(InvalidEmailError)];
+/// );
+///
+/// let errors = [ValidationError::from(InvalidEmailError), PasswordMinLengthError.into()].into_iter().collect::<ValidationErrors>();
+/// assert_eq!(errors.as_slice(), [InvalidEmailError.into(), PasswordMinLengthError.into()]);
+///
+/// let errors = [InvalidEmailError, InvalidEmailError].into_iter().collect::<ValidationErrors>();
+/// assert!(errors.is_only_invalid_email());
+///
+/// let errors = [PasswordMinLengthError].into_iter().collect::<ValidationErrors>();
+/// assert!(errors.is_only_password_min_length());
+///
+/// // Collecting produces the same elements as `extend_from`
+/// let collected = [InvalidEmailError, InvalidEmailError].into_iter().collect::<ValidationErrors>();
+/// let mut extended = ValidationErrors::default();
+/// extended.extend_from([InvalidEmailError, InvalidEmailError]);
+/// assert_eq!(collected.as_slice(), extended.as_slice());
+/// ```
+///
+/// The conversion adapter preserves the `size_hint` of the source iterator, so collecting from an `ExactSizeIterator` allocates once, like `Vec::from_iter` (the allocation-counting example of [`impl_self!`] checks this).
+#[macro_export]
+macro_rules! impl_from_iter {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<$($param,)* __VecOfEnumItem: Into<$inner>> FromIterator<__VecOfEnumItem> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn from_iter<__VecOfEnumIter: IntoIterator<Item = __VecOfEnumItem>>(iter: __VecOfEnumIter) -> Self {
+                // Goes through the `Extend` impl, which calls the `on_push` hook and evicts the oldest elements for the `ring_buffer` option
+                let mut wrapper = Self($crate::__private::Vec::new());
+                wrapper.extend(iter.into_iter().map(Into::<$inner>::into));
+                wrapper
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_from_iter!($name, [], [], $inner);
+    };
+}
+
+/// Generates `extract_first`, which removes and returns the first element matching a predicate.
+#[macro_export]
+macro_rules! impl_extract_first {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Removes and returns the first element that satisfies the predicate, preserving the order of the remaining elements.
+            ///
+            /// The removal shifts the subsequent elements, so it takes O(n) time.
+            pub fn extract_first(&mut self, pred: impl FnMut(&$inner) -> bool) -> Option<$inner> {
+                self.0
+                    .iter()
+                    .position(pred)
+                    .map(|index| self.0.remove(index))
+            }
+        }
+    };
+}
+
+/// Generates `impl From<$name> for (Vec<Payload1>, Vec<Payload2>, ...)`, which partitions the elements by variant (in the order of the `variants` list).
+///
+/// The conversion relies on the `variants` list containing every variant of the enum (which is enforced by the exhaustive `match`).
+/// The impl is generated only for up to 12 variants, because larger tuples don't implement the standard traits.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct PasswordMinLengthError(usize);
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct InvalidEmailError(&'static str);
+/// #
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     PasswordMinLength(PasswordMinLengthError),
+///     InvalidEmail(InvalidEmailError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [PasswordMinLength(PasswordMinLengthError), InvalidEmail(InvalidEmailError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError("a@example.com"));
+/// errors.push(PasswordMinLengthError(8));
+/// errors.push(InvalidEmailError("b@example.com"));
+///
+/// let (passwords, emails): (Vec<_>, Vec<_>) = errors.into();
+/// assert_eq!(passwords, [PasswordMinLengthError(8)]);
+/// assert_eq!(emails, [InvalidEmailError("a@example.com"), InvalidEmailError("b@example.com")]);
+/// ```
+#[macro_export]
+macro_rules! impl_into_vec_tuple {
+    ($name:ident, $inner:ty, [$k1:ident($p1:ty), $k2:ident($p2:ty), $k3:ident($p3:ty), $k4:ident($p4:ty), $k5:ident($p5:ty), $k6:ident($p6:ty), $k7:ident($p7:ty), $k8:ident($p8:ty), $k9:ident($p9:ty), $k10:ident($p10:ty), $k11:ident($p11:ty), $k12:ident($p12:ty), $k13:ident($p13:ty) $(, $kind:ident($payload:ty))*]) => {};
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl From<$name> for ($($crate::__private::Vec<$payload>,)+) {
+                fn from(value: $name) -> Self {
+                    type __VecOfEnumInner = $inner;
+                    $(let mut [<$kind:snake _payloads>] = $crate::__private::Vec::new();)+
+                    $crate::__private::Vec::from(value).into_iter().for_each(|element| match element {
+                        $(__VecOfEnumInner::$kind(payload) => [<$kind:snake _payloads>].push(payload),)+
+                    });
+                    ($([<$kind:snake _payloads>],)+)
+                }
+            }
+        }
+    };
+}
+
+/// Generates `clear_{variant}` methods, which remove all elements of a single variant.
+#[macro_export]
+macro_rules! impl_clear_variant {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Removes all `", stringify!($kind), "` elements without returning them. Returns the number of removed elements.")]
+                    pub fn [<clear_ $kind:snake>](&mut self) -> usize {
+                        type __VecOfEnumInner = $inner;
+                        let len = self.0.len();
+                        self.0.retain(|value| !matches!(value, __VecOfEnumInner::$kind(_)));
+                        // SAFETY: `retain` never increases the length, so the subtraction never saturates
+                        len.saturating_sub(self.0.len())
+                    }
+                )+
+            }
+        }
+    };
+}
+
+/// Generates `for_each_batch` and `into_batches` for batch-flush patterns.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     #[derive(Debug, PartialEq)]
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let size = 2;
+/// let numbers = Numbers::new(vec![1, 2, 3, 4, 5]);
+///
+/// let mut sums = Vec::new();
+/// numbers.for_each_batch(size, |batch| sums.push(batch.iter().sum::<u32>()));
+/// assert_eq!(sums, [3, 7, 5]);
+///
+/// let batches = numbers.into_batches(size);
+/// assert_eq!(batches, [Numbers::new(vec![1, 2]), Numbers::new(vec![3, 4]), Numbers::new(vec![5])]);
+/// ```
+#[macro_export]
+macro_rules! impl_batches {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Calls `f` once per consecutive batch of `size` elements (the last batch may be shorter).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `size` is zero, like `slice::chunks`.
+            pub fn for_each_batch(&self, size: usize, f: impl FnMut(&[$inner])) {
+                self.0.chunks(size).for_each(f)
+            }
+
+            /// Splits the wrapper into consecutive batches of `size` elements (the last batch may be shorter).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `size` is zero, like `slice::chunks`.
+            pub fn into_batches(self, size: usize) -> $crate::__private::Vec<Self> {
+                // A zero size would produce empty batches forever
+                assert!(size != 0, "batch size must be non-zero");
+                let mut iter = $crate::__private::Vec::from(self).into_iter().peekable();
+                ::core::iter::from_fn(|| {
+                    iter.peek()
+                        .is_some()
+                        .then(|| Self(iter.by_ref().take(size).collect()))
+                })
+                .collect()
+            }
+        }
+    };
+}
+
+/// Generates `cap_total_keeping`, which truncates the collection but never drops the elements of protected variants.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct FatalError(u32);
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct WarningError(u32);
+/// #
+/// #[derive(From, Debug, PartialEq)]
+/// pub enum Problem {
+///     Fatal(FatalError),
+///     Warning(WarningError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError)];
+/// );
+///
+/// let mut problems = Problems::default();
+/// problems.push(WarningError(1));
+/// problems.push(FatalError(2));
+/// problems.push(WarningError(3));
+/// problems.push(FatalError(4));
+///
+/// problems.cap_total_keeping(2, &[ProblemsVariantKind::Fatal]);
+/// assert_eq!(problems.as_slice(), [FatalError(2).into(), FatalError(4).into()]);
+///
+/// problems.cap_total_keeping(1, &[ProblemsVariantKind::Fatal]);
+/// assert_eq!(problems.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! impl_cap_total_keeping {
+    ($name:ident, $inner:ty) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Removes the elements that don't belong to the `protected` variant kinds, starting from the end, until the length is at most `n` (or until no such elements remain).
+                ///
+                /// The elements of the protected variant kinds are never removed, so the resulting length may exceed `n`.
+                pub fn cap_total_keeping(&mut self, n: usize, protected: &[[<$name VariantKind>]]) {
+                    // SAFETY: the number of excess elements is zero if the length doesn't exceed `n`
+                    let mut excess = self.0.len().saturating_sub(n);
+                    self.0.reverse();
+                    self.0.retain(|value| {
+                        let kind = [<$name VariantKind>]::from(value);
+                        let is_protected = protected.iter().any(|protected_kind| ::core::mem::discriminant(protected_kind) == ::core::mem::discriminant(&kind));
+                        match excess.checked_sub(1) {
+                            Some(rest) if !is_protected => {
+                                excess = rest;
+                                false
+                            }
+                            _ => true,
+                        }
+                    });
+                    self.0.reverse();
+                }
+            }
+        }
+    };
+}
+
+/// Generates `is_only_variant` and `is_only_{variant}` methods, which check that a non-empty collection contains a single variant.
+#[macro_export]
+macro_rules! impl_is_only_variant {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Returns `true` if the collection is non-empty and every element is of the given variant kind.
+                pub fn is_only_variant(&self, kind: [<$name VariantKind>]) -> bool {
+                    !self.0.is_empty() && self.0.iter().all(|value| ::core::mem::discriminant(&[<$name VariantKind>]::from(value)) == ::core::mem::discriminant(&kind))
+                }
+
+                $(
+                    #[doc = concat!("Returns `true` if the collection is non-empty and every element is `", stringify!($kind), "`.")]
+                    pub fn [<is_only_ $kind:snake>](&self) -> bool {
+                        type __VecOfEnumInner = $inner;
+                        !self.0.is_empty() && self.0.iter().all(|value| matches!(value, __VecOfEnumInner::$kind(_)))
+                    }
+                )+
+            }
+        }
+    };
+}
+
+/// Generates `sort_dedup` and `sort_dedup_by_key`, which sort the collection and remove the duplicates.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let mut numbers = Numbers::new(vec![3, 1, 3, 2, 1]);
+/// assert_eq!(numbers.sort_dedup(), 2);
+/// assert_eq!(numbers.as_slice(), [1, 2, 3]);
+///
+/// let mut numbers = Numbers::new(vec![13, 1, 3, 22, 11]);
+/// assert_eq!(numbers.sort_dedup_by_key(|number| number % 10), 2);
+/// assert_eq!(numbers.as_slice(), [1, 22, 13]);
+/// ```
+#[macro_export]
+macro_rules! impl_sort_dedup {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Sorts the elements and removes the duplicates, returning the number of removed elements. Requires the element type to implement `Ord`.
+            pub fn sort_dedup(&mut self) -> usize
+            where
+                for<'a> $inner: Ord,
+            {
+                let len = self.0.len();
+                self.0.sort();
+                self.0.dedup();
+                // SAFETY: `dedup` never increases the length, so the subtraction never saturates
+                len.saturating_sub(self.0.len())
+            }
+
+            /// Sorts the elements by key and removes the elements with duplicate keys (keeping the first one in the sorted order), returning the number of removed elements.
+            pub fn sort_dedup_by_key<__VecOfEnumKey: Ord>(&mut self, mut key: impl FnMut(&$inner) -> __VecOfEnumKey) -> usize {
+                let len = self.0.len();
+                self.0.sort_by_key(&mut key);
+                self.0.dedup_by(|a, b| key(a) == key(b));
+                // SAFETY: `dedup_by` never increases the length, so the subtraction never saturates
+                len.saturating_sub(self.0.len())
+            }
+        }
+    };
+}
+
+/// Generates `replace_with`, which rewrites the whole inner vector.
+#[macro_export]
+macro_rules! impl_replace_with {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Replaces the inner vector with the result of `f` applied to it. Unlike element-wise methods, `f` may change the length and the order of the elements.
+            ///
+            /// The inner vector is temporarily replaced with an empty vector while `f` runs, so the collection stays empty if `f` panics.
+            pub fn replace_with(&mut self, f: impl FnOnce($crate::__private::Vec<$inner>) -> $crate::__private::Vec<$inner>) {
+                self.0 = f(::core::mem::take(&mut self.0));
+            }
+        }
+    };
+}
+
+/// Generates `take_while_owned` and `drop_while`, which split the collection at the end of the leading run of elements that satisfy a predicate.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let numbers = Numbers::new(vec![1, 2, 10, 3]);
+/// assert_eq!(numbers.take_while_owned(|number| *number < 5).as_slice(), [1, 2]);
+///
+/// let numbers = Numbers::new(vec![1, 2, 10, 3]);
+/// assert_eq!(numbers.drop_while(|number| *number < 5).as_slice(), [10, 3]);
+/// ```
+#[macro_export]
+macro_rules! impl_take_while {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns the leading elements that satisfy the predicate, dropping the rest (starting from the first element that doesn't satisfy it).
+            pub fn take_while_owned(mut self, mut pred: impl FnMut(&$inner) -> bool) -> Self {
+                let count = self.0.iter().take_while(|value| pred(value)).count();
+                self.0.truncate(count);
+                self
+            }
+
+            /// Returns the elements starting from the first element that doesn't satisfy the predicate, dropping the leading elements that satisfy it.
+            pub fn drop_while(mut self, mut pred: impl FnMut(&$inner) -> bool) -> Self {
+                let count = self.0.iter().take_while(|value| pred(value)).count();
+                self.0.drain(..count);
+                self
+            }
+        }
+    };
+}
+
+/// Generates `snapshot` and `restore`, which provide savepoints for speculative accumulation.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let mut messages = Messages::default();
+/// messages.push("kept");
+/// let marker = messages.snapshot();
+/// messages.push("rolled back");
+/// messages.restore(marker);
+/// assert_eq!(messages.as_slice(), ["kept"]);
+/// ```
+#[macro_export]
+macro_rules! impl_snapshot {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns a marker for the current state, which can be passed to [`restore`](Self::restore) to roll back the elements pushed after it.
+            ///
+            /// The marker is the current length, so it is invalidated by operations that remove or reorder the elements before it.
+            pub fn snapshot(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Removes the elements added after the [`snapshot`](Self::snapshot) that returned the `marker`. Does nothing if the collection is not longer than the marker.
+            pub fn restore(&mut self, marker: usize) {
+                self.0.truncate(marker)
+            }
+        }
+    };
+}
+
+/// Generates `extend_until_variant`, which extends the collection until it adds an element of the given variant kind.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct FatalError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct WarningError;
+/// #
+/// #[derive(From, Debug)]
+/// pub enum Problem {
+///     Fatal(FatalError),
+///     Warning(WarningError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError)];
+/// );
+///
+/// let mut problems = Problems::default();
+/// let source = [Problem::from(WarningError), FatalError.into(), WarningError.into()];
+/// assert!(problems.extend_until_variant(source, ProblemsVariantKind::Fatal));
+/// assert_eq!(problems.len(), 2);
+///
+/// assert!(!problems.extend_until_variant([WarningError], ProblemsVariantKind::Fatal));
+/// assert_eq!(problems.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! impl_extend_until_variant {
+    ($name:ident, $inner:ty) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Pushes the elements until it pushes an element of the `stop` variant kind (inclusive), leaving the rest of the iterator unconsumed. Returns `true` if it pushed such an element.
+                pub fn extend_until_variant(&mut self, iter: impl IntoIterator<Item = impl Into<$inner>>, stop: [<$name VariantKind>]) -> bool {
+                    let stop = ::core::mem::discriminant(&stop);
+                    iter.into_iter().map(Into::into).any(|value: $inner| {
+                        let is_stop = ::core::mem::discriminant(&[<$name VariantKind>]::from(&value)) == stop;
+                        self.push(value);
+                        is_stop
+                    })
+                }
+            }
+        }
+    };
+}
+
+/// Generates `into_first`, `into_most_severe` and `summarize`, which collapse the collection into a single element.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     #[derive(Debug)]
+///     pub struct Severities(Vec<u32>);
+/// );
+///
+/// assert_eq!(Severities::new(vec![2, 5, 1]).into_first(), Some(2));
+/// assert_eq!(Severities::new(vec![2, 5, 1, 5]).into_most_severe(|a, b| a > b), Some(5));
+/// assert_eq!(Severities::default().into_most_severe(|a, b| a > b), None);
+///
+/// vec_of_enum::define!(
+///     #[derive(Debug)]
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let messages = Messages::new(vec!["disk full".to_string(), "timeout".to_string()]);
+/// let summary = messages.summarize(|messages| format!("{} errors occurred", messages.len()));
+/// assert_eq!(summary.as_slice(), ["2 errors occurred"]);
+/// // An empty collection stays empty
+/// assert!(Messages::default().summarize(|_| unreachable!()).is_empty());
+/// ```
+#[macro_export]
+macro_rules! impl_into_single {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns the first element, dropping the rest.
+            pub fn into_first(self) -> Option<$inner> {
+                $crate::__private::Vec::from(self).into_iter().next()
+            }
+
+            /// Returns the worst element, dropping the rest. `worse(a, b)` must return `true` if `a` is worse than `b`; the first of the equally bad elements wins.
+            pub fn into_most_severe(self, mut worse: impl FnMut(&$inner, &$inner) -> bool) -> Option<$inner> {
+                $crate::__private::Vec::from(self)
+                    .into_iter()
+                    .reduce(|worst, value| if worse(&value, &worst) { value } else { worst })
+            }
+
+            /// Replaces the elements with a single summary element computed by `f`. Returns an empty collection without calling `f` if the collection is empty, so the result contains at most one element.
+            pub fn summarize(self, f: impl FnOnce($crate::__private::Vec<$inner>) -> $inner) -> Self {
+                let vec = $crate::__private::Vec::from(self);
+                if vec.is_empty() { Self(vec) } else { Self::new($crate::__private::vec![f(vec)]) }
+            }
+        }
+    };
+}
+
+/// Generates `try_get`, which returns an [`IndexOutOfBounds`] error with the context instead of `None`.
+///
+/// ```rust
+/// use vec_of_enum::IndexOutOfBounds;
+///
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let numbers = Numbers::new(vec![1, 2]);
+/// assert_eq!(numbers.try_get(1), Ok(&2));
+/// assert_eq!(numbers.try_get(3), Err(IndexOutOfBounds::new(3, 2)));
+/// ```
+#[macro_export]
+macro_rules! impl_try_get {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns a reference to the element at `index`, or an error with the index and the length if the index is out of bounds.
+            pub fn try_get(&self, index: usize) -> Result<&$inner, $crate::IndexOutOfBounds> {
+                self.0
+                    .get(index)
+                    .ok_or_else(|| $crate::IndexOutOfBounds::new(index, self.0.len()))
+            }
+        }
+    };
+}
+
+/// Generates `par_retain` and `par_into_filtered`, which filter the elements in parallel with `rayon`.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let mut numbers = Numbers::new((0..1000).collect::<Vec<_>>());
+/// numbers.par_retain(|number| number % 2 == 0);
+/// assert_eq!(numbers.len(), 500);
+/// assert_eq!(numbers.first(), Some(&0));
+/// assert_eq!(numbers.last(), Some(&998));
+///
+/// let numbers = numbers.par_into_filtered(|number| number % 10 == 0);
+/// assert_eq!(numbers.len(), 100);
+/// ```
+#[cfg(feature = "rayon")]
+#[macro_export]
+macro_rules! impl_par_retain {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Retains only the elements that satisfy the predicate, evaluating it in parallel and keeping the original order. Requires the element type to implement `Send`.
+            pub fn par_retain(&mut self, pred: impl Fn(&$inner) -> bool + Sync)
+            where
+                for<'a> $inner: Send,
+            {
+                use $crate::__private::rayon::iter::{IntoParallelIterator, ParallelIterator};
+                self.0 = ::core::mem::take(&mut self.0)
+                    .into_par_iter()
+                    .filter(|value| pred(value))
+                    .collect();
+            }
 
-#![deny(clippy::arithmetic_side_effects)]
-#![cfg_attr(not(test), deny(unused_crate_dependencies))]
+            /// Consumes the collection and returns the elements that satisfy the predicate, evaluating it in parallel and keeping the original order. Requires the element type to implement `Send`.
+            pub fn par_into_filtered(mut self, pred: impl Fn(&$inner) -> bool + Sync) -> Self
+            where
+                for<'a> $inner: Send,
+            {
+                self.par_retain(pred);
+                self
+            }
+        }
+    };
+}
 
+#[cfg(not(feature = "rayon"))]
 #[macro_export]
-macro_rules! define {
-    (
-        $(#[$meta:meta])*
-        $vis:vis struct $name:ident(Vec<$inner:ty>)
-        $(where [$($where_clause:tt)*])?;
-        $(variants = [$($variant:ty),+];)?
-    ) => {
-        $crate::define_struct!(
-            $(#[$meta])*
-            $vis struct $name(Vec<$inner>)
-            $(where [$($where_clause)*])?;
-        );
-        $crate::impl_self!($name, $inner);
-        $crate::impl_default!($name);
-        $crate::impl_extend!($name, $inner);
-        $crate::impl_into_iter_own!($name, $inner);
-        $crate::impl_into_iter_ref!($name, $inner);
-        $crate::impl_deref!($name, $inner);
-        $crate::impl_deref_mut!($name, $inner);
-        $crate::impl_from_vec!($name, $inner);
-        $crate::impl_into_vec!($name, $inner);
-        $($crate::impl_from_value!($name, [$($variant),+]);)?
+macro_rules! impl_par_retain {
+    ($name:ident, $inner:ty) => {};
+}
+
+/// Generates `merge_duplicate_{variant}` methods, which fold the consecutive elements of a single variant into the first one of every run.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(Debug)]
+/// pub struct InvalidEmailError {
+///     reasons: Vec<&'static str>,
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct PasswordMinLengthError;
+///
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     InvalidEmail(InvalidEmailError),
+///     PasswordMinLength(PasswordMinLengthError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [InvalidEmail(InvalidEmailError), PasswordMinLength(PasswordMinLengthError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError { reasons: vec!["is blocked"] });
+/// errors.push(InvalidEmailError { reasons: vec!["is too long"] });
+/// errors.push(PasswordMinLengthError);
+/// errors.push(InvalidEmailError { reasons: vec!["has no domain"] });
+///
+/// let merged = errors.merge_duplicate_invalid_email(|first, later| first.reasons.extend(later.reasons));
+/// assert_eq!(merged, 1);
+/// assert_eq!(errors.len(), 3);
+/// assert!(matches!(errors.first(), Some(ValidationError::InvalidEmail(error)) if error.reasons == ["is blocked", "is too long"]));
+/// ```
+#[macro_export]
+macro_rules! impl_merge_duplicate_variant {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Folds every `", stringify!($kind), "` element that directly follows another `", stringify!($kind), "` element into the first element of the run via `merge`. Returns the number of merged elements.")]
+                    pub fn [<merge_duplicate_ $kind:snake>](&mut self, mut merge: impl FnMut(&mut $payload, $payload)) -> usize {
+                        type __VecOfEnumInner = $inner;
+                        let len = self.0.len();
+                        let mut merged = $crate::__private::Vec::with_capacity(len);
+                        for value in ::core::mem::take(&mut self.0) {
+                            match value {
+                                __VecOfEnumInner::$kind(later) => match merged.last_mut() {
+                                    Some(__VecOfEnumInner::$kind(first)) => merge(first, later),
+                                    _ => merged.push(__VecOfEnumInner::$kind(later)),
+                                },
+                                #[allow(unreachable_patterns)]
+                                value => merged.push(value),
+                            }
+                        }
+                        self.0 = merged;
+                        // SAFETY: merging never increases the length, so the subtraction never saturates
+                        len.saturating_sub(self.0.len())
+                    }
+                )+
+            }
+        }
     };
 }
 
+/// Generates `deserialize_extend`, which deserializes a sequence and appends its elements to the existing collection.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let mut messages = Messages::new(vec!["hello".to_string()]);
+/// messages.deserialize_extend(&mut serde_json::Deserializer::from_str(r#"["world", "again"]"#))?;
+/// assert_eq!(messages.as_slice(), ["hello", "world", "again"]);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[cfg(feature = "serde")]
 #[macro_export]
-macro_rules! define_struct {
-    (
-        $(#[$meta:meta])*
-        $vis:vis struct $name:ident(Vec<$inner:ty>)
-        $(where [$($where_clause:tt)*])?;
-    ) => {
-        #[repr(transparent)]
-        $(#[$meta])*
-        $vis struct $name(Vec<$inner>)
-        $(where $($where_clause)*)?;
+macro_rules! impl_deserialize_extend {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Deserializes a sequence and appends its elements without constructing a temporary wrapper. On error, the elements deserialized before the error remain in the collection.
+            pub fn deserialize_extend<'de, __VecOfEnumDeserializer: $crate::__private::serde::Deserializer<'de>>(&mut self, deserializer: __VecOfEnumDeserializer) -> Result<(), __VecOfEnumDeserializer::Error>
+            where
+                for<'a> $inner: $crate::__private::serde::Deserialize<'de>,
+            {
+                $crate::__private::deserialize_extend(deserializer, self)
+            }
+        }
     };
 }
 
+#[cfg(not(feature = "serde"))]
 #[macro_export]
-macro_rules! impl_self {
+macro_rules! impl_deserialize_extend {
+    ($name:ident, $inner:ty) => {};
+}
+
+/// Generates `find_map_{variant}` methods, which return the first `Some` computed from the payloads of a single variant.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(Debug)]
+/// pub struct InvalidEmailError {
+///     email: String,
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct PasswordMinLengthError;
+///
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     InvalidEmail(InvalidEmailError),
+///     PasswordMinLength(PasswordMinLengthError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [InvalidEmail(InvalidEmailError), PasswordMinLength(PasswordMinLengthError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(PasswordMinLengthError);
+/// errors.push(InvalidEmailError { email: "a".into() });
+/// errors.push(InvalidEmailError { email: "b@example.com".into() });
+///
+/// let domain = errors.find_map_invalid_email(|error| error.email.split_once('@').map(|(_, domain)| domain.to_string()));
+/// assert_eq!(domain.as_deref(), Some("example.com"));
+/// ```
+#[macro_export]
+macro_rules! impl_find_map_variant {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Applies `f` to the `", stringify!($kind), "` payloads in order and returns the first `Some` result.")]
+                    pub fn [<find_map_ $kind:snake>]<__VecOfEnumOutput>(&self, mut f: impl FnMut(&$payload) -> Option<__VecOfEnumOutput>) -> Option<__VecOfEnumOutput> {
+                        type __VecOfEnumInner = $inner;
+                        self.0.iter().find_map(|value| match value {
+                            __VecOfEnumInner::$kind(payload) => f(payload),
+                            #[allow(unreachable_patterns)]
+                            _ => None,
+                        })
+                    }
+                )+
+            }
+        }
+    };
+}
+
+/// Generates a `Drop` impl that emits a `tracing` warning when a non-empty collection is dropped.
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! impl_warn_on_drop {
+    ($name:ident, $inner:ty) => {
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if !self.0.is_empty() {
+                    $crate::__private::tracing::warn!(len = self.0.len(), "`{}` dropped with unconsumed elements", stringify!($name));
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! impl_warn_on_drop {
+    ($name:ident, $inner:ty) => {
+        compile_error!("the `warn_on_drop` option requires the `tracing` feature of `vec-of-enum`");
+    };
+}
+
+/// Generates `iter_peekable`, which returns a peekable iterator over the elements.
+#[macro_export]
+macro_rules! impl_iter_peekable {
     ($name:ident, $inner:ty) => {
         impl $name {
-            pub fn new(inner: impl Into<Vec<$inner>>) -> Self {
-                Self(inner.into())
+            /// Returns a peekable iterator over the elements, for the sequential consumption that looks at the next element before handling it.
+            pub fn iter_peekable(&self) -> ::core::iter::Peekable<::core::slice::Iter<'_, $inner>> {
+                self.0.iter().peekable()
             }
+        }
+    };
+}
 
-            pub fn push(&mut self, value: impl Into<$inner>) {
-                self.0.push(value.into())
+/// Generates `peek_is_variant`, which checks the variant kind of the next element of a peekable iterator.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct FatalError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct WarningError;
+/// #
+/// #[derive(From, Debug)]
+/// pub enum Problem {
+///     Fatal(FatalError),
+///     Warning(WarningError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError)];
+/// );
+///
+/// let mut problems = Problems::default();
+/// problems.push(WarningError);
+/// problems.push(WarningError);
+/// problems.push(FatalError);
+///
+/// let mut iter = problems.iter_peekable();
+/// let mut warnings = 0;
+/// while Problems::peek_is_variant(&mut iter, ProblemsVariantKind::Warning) {
+///     iter.next();
+///     warnings += 1;
+/// }
+/// assert_eq!(warnings, 2);
+/// assert!(Problems::peek_is_variant(&mut iter, ProblemsVariantKind::Fatal));
+/// ```
+#[macro_export]
+macro_rules! impl_peek_is_variant {
+    ($name:ident, $inner:ty) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Returns `true` if the next element of the iterator is of the given variant kind, without consuming it.
+                pub fn peek_is_variant(iter: &mut ::core::iter::Peekable<::core::slice::Iter<'_, $inner>>, kind: [<$name VariantKind>]) -> bool {
+                    iter.peek()
+                        .is_some_and(|value| ::core::mem::discriminant(&[<$name VariantKind>]::from(*value)) == ::core::mem::discriminant(&kind))
+                }
             }
+        }
+    };
+}
 
-            pub fn extend_from<T: Into<$inner>>(&mut self, iter: impl IntoIterator<Item = T>) {
-                self.extend(iter.into_iter().map(T::into))
+/// Generates `retain_map_{variant}` methods, which transform the payloads of a single variant and remove the ones mapped to `None`.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(Debug)]
+/// pub struct InvalidEmailError {
+///     email: String,
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct PasswordMinLengthError;
+///
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     InvalidEmail(InvalidEmailError),
+///     PasswordMinLength(PasswordMinLengthError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [InvalidEmail(InvalidEmailError), PasswordMinLength(PasswordMinLengthError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError { email: " A@example.com ".into() });
+/// errors.push(PasswordMinLengthError);
+/// errors.push(InvalidEmailError { email: "  ".into() });
+///
+/// let removed = errors.retain_map_invalid_email(|error| {
+///     let email = error.email.trim().to_lowercase();
+///     (!email.is_empty()).then_some(InvalidEmailError { email })
+/// });
+/// assert_eq!(removed, 1);
+/// assert_eq!(errors.len(), 2);
+/// assert!(matches!(errors.first(), Some(ValidationError::InvalidEmail(error)) if error.email == "a@example.com"));
+/// ```
+#[macro_export]
+macro_rules! impl_retain_map_variant {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Replaces every `", stringify!($kind), "` payload with the result of `f`, removing the elements mapped to `None`. The other variants are kept as is. Returns the number of removed elements.")]
+                    pub fn [<retain_map_ $kind:snake>](&mut self, mut f: impl FnMut($payload) -> Option<$payload>) -> usize {
+                        type __VecOfEnumInner = $inner;
+                        let len = self.0.len();
+                        self.0 = ::core::mem::take(&mut self.0)
+                            .into_iter()
+                            .filter_map(|value| match value {
+                                __VecOfEnumInner::$kind(payload) => f(payload).map(__VecOfEnumInner::$kind),
+                                #[allow(unreachable_patterns)]
+                                value => Some(value),
+                            })
+                            .collect();
+                        // SAFETY: `filter_map` never increases the length, so the subtraction never saturates
+                        len.saturating_sub(self.0.len())
+                    }
+                )+
             }
         }
     };
 }
 
+/// Generates `variant_bitset`, which summarizes the variant kinds present in the collection as a bitset.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct FatalError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct WarningError;
+/// #
+/// #[derive(From, Debug)]
+/// pub enum Problem {
+///     Fatal(FatalError),
+///     Warning(WarningError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError)];
+/// );
+///
+/// let mut problems = Problems::default();
+/// assert_eq!(problems.variant_bitset(), 0b00);
+/// problems.push(WarningError);
+/// problems.push(WarningError);
+/// assert_eq!(problems.variant_bitset(), 0b10);
+/// problems.push(FatalError);
+/// assert_eq!(problems.variant_bitset(), 0b11);
+/// ```
 #[macro_export]
-macro_rules! impl_default {
-    ($name:ident) => {
-        impl Default for $name {
-            fn default() -> Self {
-                Self(Default::default())
+macro_rules! impl_variant_bitset {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        // Consumes one unit of the budget per variant, so the method is generated only if the budget (64 units) covers every variant
+        $crate::impl_variant_bitset!(@limit $name, $inner, [$($kind)+], [x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x]);
+    };
+    (@limit $name:ident, $inner:ty, [], [$($budget:ident)*]) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Returns a bitset of the variant kinds present in the collection, where the bit at the position of the variant (in the `variants` option) is set if at least one element is of that variant.
+                ///
+                /// Two collections contain the same variant kinds if their bitsets are equal. The method is generated only if there are at most 64 variants.
+                pub fn variant_bitset(&self) -> u64 {
+                    self.0
+                        .iter()
+                        // There are at most 64 variants, so the shift never overflows
+                        .map(|value| 1u64.checked_shl([<$name VariantKind>]::from(value) as u32).unwrap_or_default())
+                        .fold(0, |bitset, bit| bitset | bit)
+                }
             }
         }
     };
+    (@limit $name:ident, $inner:ty, [$($kind:ident)+], []) => {};
+    (@limit $name:ident, $inner:ty, [$kind:ident $($kinds:ident)*], [$budget:ident $($budgets:ident)*]) => {
+        $crate::impl_variant_bitset!(@limit $name, $inner, [$($kinds)*], [$($budgets)*]);
+    };
 }
 
+/// Generates `concat_all`, which flattens many wrappers into one.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let parts = [Messages::new(vec!["a", "b"]), Messages::default(), Messages::new(vec!["c"])];
+/// let messages = Messages::concat_all(parts);
+/// assert_eq!(messages.as_slice(), ["a", "b", "c"]);
+/// assert_eq!(messages.capacity(), 3);
+/// ```
 #[macro_export]
-macro_rules! impl_extend {
+macro_rules! impl_concat_all {
     ($name:ident, $inner:ty) => {
-        impl Extend<$inner> for $name {
-            fn extend<I: IntoIterator<Item = $inner>>(&mut self, iter: I) {
-                self.0.extend(iter);
+        impl $name {
+            /// Concatenates the wrappers into one, preserving the order: the elements of every wrapper are appended in the iteration order.
+            ///
+            /// Reserves the total capacity once before appending the elements.
+            pub fn concat_all(iter: impl IntoIterator<Item = Self>) -> Self {
+                let wrappers = iter.into_iter().collect::<$crate::__private::Vec<_>>();
+                // SAFETY: the lengths are bounded by the memory of the existing wrappers; a saturated total makes the reservation fail like any other capacity overflow
+                let len = wrappers
+                    .iter()
+                    .fold(0usize, |len, wrapper| len.saturating_add(wrapper.0.len()));
+                let mut vec = $crate::__private::Vec::with_capacity(len);
+                vec.extend(wrappers.into_iter().flatten());
+                Self(vec)
             }
         }
     };
 }
 
+/// Generates `clone_into`, which clones the elements into an existing wrapper.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<String>);
+/// );
+///
+/// let source = Messages::new(vec!["hello".to_string(), "world".to_string()]);
+/// let mut dest = Messages::new(Vec::with_capacity(16));
+/// dest.push("stale".to_string());
+/// let ptr = dest.as_ptr();
+///
+/// source.clone_into(&mut dest);
+/// assert_eq!(dest.as_slice(), ["hello", "world"]);
+/// assert_eq!(dest.as_ptr(), ptr);
+/// assert_eq!(dest.capacity(), 16);
+/// ```
 #[macro_export]
-macro_rules! impl_into_iter_own {
+macro_rules! impl_clone_into {
     ($name:ident, $inner:ty) => {
-        impl IntoIterator for $name {
-            type Item = $inner;
-            type IntoIter = std::vec::IntoIter<Self::Item>;
+        impl $name {
+            /// Replaces the elements of `dest` with the clones of the elements of `self`, like `ToOwned::clone_into` for slices. Reuses the allocation of `dest` if its capacity suffices. Requires the element type to implement `Clone`.
+            pub fn clone_into(&self, dest: &mut Self)
+            where
+                for<'a> $inner: Clone,
+            {
+                $crate::__private::ToOwned::clone_into(self.0.as_slice(), &mut dest.0)
+            }
+        }
+    };
+}
 
-            fn into_iter(self) -> Self::IntoIter {
-                self.0.into_iter()
+/// Generates `impl From<$name> for Result<(), Vec<$inner>>`, which consumes the wrapper and maps an empty collection to `Ok(())` and a non-empty one to `Err` with the elements.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// fn check(messages: Messages) -> Result<(), Vec<&'static str>> {
+///     messages.into()
+/// }
+///
+/// assert_eq!(check(Messages::default()), Ok(()));
+/// assert_eq!(check(Messages::new(vec!["is blocked"])), Err(vec!["is blocked"]));
+/// ```
+#[macro_export]
+macro_rules! impl_into_result {
+    ($name:ident, $inner:ty) => {
+        impl From<$name> for Result<(), $crate::__private::Vec<$inner>> {
+            fn from(value: $name) -> Self {
+                let vec = $crate::__private::Vec::from(value);
+                if vec.is_empty() { Ok(()) } else { Err(vec) }
             }
         }
     };
 }
 
+/// Generates `dedup_global_by_key`, which keeps only the first element per key, preserving the order.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct FieldErrors(Vec<(&'static str, &'static str)>);
+/// );
+///
+/// let mut errors = FieldErrors::new(vec![("email", "is blocked"), ("password", "is too short"), ("email", "is too long")]);
+/// let removed = errors.dedup_global_by_key(|(field, _)| *field);
+/// assert_eq!(removed, 1);
+/// assert_eq!(errors.as_slice(), [("email", "is blocked"), ("password", "is too short")]);
+/// ```
+#[cfg(feature = "std")]
 #[macro_export]
-macro_rules! impl_into_iter_ref {
+macro_rules! impl_dedup_global_by_key {
     ($name:ident, $inner:ty) => {
-        impl<'a> IntoIterator for &'a $name {
-            type Item = &'a $inner;
-            type IntoIter = std::slice::Iter<'a, $inner>;
+        impl $name {
+            /// Keeps only the first element for every distinct key, removing the later duplicates anywhere in the collection (unlike `Vec::dedup_by_key`, which removes only the consecutive ones). Preserves the order of the kept elements. Returns the number of removed elements.
+            pub fn dedup_global_by_key<__VecOfEnumKey: Eq + ::core::hash::Hash>(&mut self, mut key: impl FnMut(&$inner) -> __VecOfEnumKey) -> usize {
+                let mut seen = $crate::__private::HashSet::new();
+                let len = self.0.len();
+                self.0.retain(|value| seen.insert(key(value)));
+                // SAFETY: `retain` never increases the length, so the subtraction never saturates
+                len.saturating_sub(self.0.len())
+            }
+        }
+    };
+}
 
-            fn into_iter(self) -> Self::IntoIter {
-                self.0.iter()
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! impl_dedup_global_by_key {
+    ($name:ident, $inner:ty) => {};
+}
+
+/// Generates `dedup_global_by_key_ord`, which keeps only the first element per key, like `dedup_global_by_key`, but stores the seen keys in a `BTreeSet` (the keys must implement `Ord` instead of `Hash`).
+///
+/// Unlike `dedup_global_by_key`, the method doesn't require the `std` feature.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct FieldErrors(Vec<(&'static str, &'static str)>);
+/// );
+///
+/// let mut errors = FieldErrors::new(vec![("email", "is blocked"), ("password", "is too short"), ("email", "is too long")]);
+/// assert_eq!(errors.dedup_global_by_key_ord(|(field, _)| *field), 1);
+/// assert_eq!(errors.as_slice(), [("email", "is blocked"), ("password", "is too short")]);
+/// ```
+#[macro_export]
+macro_rules! impl_dedup_global_by_key_ord {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Keeps only the first element for every distinct key, removing the later duplicates anywhere in the collection (unlike `Vec::dedup_by_key`, which removes only the consecutive ones). Preserves the order of the kept elements. Returns the number of removed elements.
+            pub fn dedup_global_by_key_ord<__VecOfEnumKey: Ord>(&mut self, mut key: impl FnMut(&$inner) -> __VecOfEnumKey) -> usize {
+                let mut seen = $crate::__private::BTreeSet::new();
+                let len = self.0.len();
+                self.0.retain(|value| seen.insert(key(value)));
+                // SAFETY: `retain` never increases the length, so the subtraction never saturates
+                len.saturating_sub(self.0.len())
             }
         }
     };
 }
 
+/// Generates `iter_{variant}_mut` methods, which iterate over the mutable payloads of a single variant.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(Debug)]
+/// pub struct InvalidEmailError {
+///     email: String,
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct PasswordMinLengthError;
+///
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     InvalidEmail(InvalidEmailError),
+///     PasswordMinLength(PasswordMinLengthError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [InvalidEmail(InvalidEmailError), PasswordMinLength(PasswordMinLengthError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError { email: "A@example.com".into() });
+/// errors.push(PasswordMinLengthError);
+/// errors.push(InvalidEmailError { email: "B@example.com".into() });
+///
+/// if let Some(error) = errors.iter_invalid_email_mut().next() {
+///     error.email.make_ascii_lowercase();
+/// }
+/// assert!(matches!(errors.first(), Some(ValidationError::InvalidEmail(error)) if error.email == "a@example.com"));
+/// assert!(matches!(errors.last(), Some(ValidationError::InvalidEmail(error)) if error.email == "B@example.com"));
+///
+/// fn is_fused(_: &impl std::iter::FusedIterator) -> bool {
+///     true
+/// }
+/// let mut iter = errors.iter_invalid_email_mut();
+/// assert!(is_fused(&iter));
+/// assert_eq!(iter.next_back().map(|error| error.email.as_str()), Some("B@example.com"));
+/// ```
 #[macro_export]
-macro_rules! impl_deref {
+macro_rules! impl_iter_variant_mut {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Returns an iterator over the mutable payloads of the `", stringify!($kind), "` elements.")]
+                    pub fn [<iter_ $kind:snake _mut>](&mut self) -> impl DoubleEndedIterator<Item = &mut $payload> + ::core::iter::FusedIterator {
+                        type __VecOfEnumInner = $inner;
+                        self.0.iter_mut().filter_map(|value| match value {
+                            __VecOfEnumInner::$kind(payload) => Some(payload),
+                            #[allow(unreachable_patterns)]
+                            _ => None,
+                        })
+                    }
+                )+
+            }
+        }
+    };
+}
+
+/// Generates `into_vec_compact`, which converts the wrapper into a `Vec` without the excess capacity.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let mut messages = Messages::new(Vec::with_capacity(16));
+/// messages.push("hello");
+///
+/// let vec = messages.into_vec_compact();
+/// assert_eq!(vec, ["hello"]);
+/// assert_eq!(vec.capacity(), 1);
+/// ```
+#[macro_export]
+macro_rules! impl_into_vec_compact {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Converts the wrapper into a `Vec`, shrinking its capacity to fit the length (which may reallocate). Use `Into<Vec<T>>` to keep the capacity instead.
+            pub fn into_vec_compact(self) -> $crate::__private::Vec<$inner> {
+                let mut vec = $crate::__private::Vec::from(self);
+                vec.shrink_to_fit();
+                vec
+            }
+        }
+    };
+}
+
+/// Generates `as_ffi_parts` and the unsafe `from_raw_parts`, which support passing the elements across an FFI boundary.
+///
+/// ```rust
+/// use std::mem::ManuallyDrop;
+///
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// let numbers = Numbers::new(vec![1, 2, 3]);
+/// let (ptr, len) = numbers.as_ffi_parts();
+/// // SAFETY: `ptr` points to `len` initialized elements while `numbers` is alive and not mutated
+/// assert_eq!(unsafe { std::slice::from_raw_parts(ptr, len) }, [1, 2, 3]);
+///
+/// let mut vec = ManuallyDrop::new(Vec::from(numbers));
+/// let (ptr, len, capacity) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+/// // SAFETY: the parts come from a `Vec<u32>` that is never dropped
+/// let numbers = unsafe { Numbers::from_raw_parts(ptr, len, capacity) };
+/// assert_eq!(numbers.as_slice(), [1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! impl_ffi_parts {
     ($name:ident, $inner:ty) => {
-        impl std::ops::Deref for $name {
-            type Target = Vec<$inner>;
+        impl $name {
+            /// Returns the pointer to the first element and the number of elements, for sharing the elements read-only with foreign code.
+            ///
+            /// The collection keeps the ownership: the pointer is valid only while the collection is alive and isn't mutated, and the foreign code must not write through it or free it. The pointer is dangling (but non-null and aligned) if the collection is empty.
+            pub fn as_ffi_parts(&self) -> (*const $inner, usize) {
+                (self.0.as_ptr(), self.0.len())
+            }
 
-            fn deref(&self) -> &Self::Target {
-                &self.0
+            /// Creates a collection directly from a pointer, a length and a capacity, taking the ownership of the allocation.
+            ///
+            /// # Safety
+            ///
+            /// The parts must satisfy the safety requirements of [`Vec::from_raw_parts`], which is the case if they come from a `Vec` of the element type that was allocated by the global allocator and is never used or dropped afterwards (e.g. it was wrapped in `ManuallyDrop`).
+            pub unsafe fn from_raw_parts(ptr: *mut $inner, length: usize, capacity: usize) -> Self {
+                // SAFETY: the caller upholds the requirements of `Vec::from_raw_parts`
+                Self(unsafe { $crate::__private::Vec::from_raw_parts(ptr, length, capacity) })
             }
         }
     };
 }
 
+/// Generates `PartialEq` impls (in both directions) between the wrapper and `Vec<$inner>`, `[$inner]` and `&[$inner]`.
+///
+/// The impls require the element type to implement `PartialEq`; for other element types they exist but can't be used.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     #[derive(Debug)]
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let messages = Messages::new(vec!["a", "b"]);
+/// assert_eq!(messages, vec!["a", "b"]);
+/// assert_ne!(messages, vec!["a"]);
+/// assert_eq!(vec!["a", "b"], messages);
+/// assert_eq!(messages, ["a", "b"][..]);
+/// assert_eq!(messages, &["a", "b"][..]);
+/// assert_ne!(&["b", "a"][..], messages);
+/// ```
 #[macro_export]
-macro_rules! impl_deref_mut {
+macro_rules! impl_partial_eq_vec {
     ($name:ident, $inner:ty) => {
-        impl std::ops::DerefMut for $name {
-            fn deref_mut(&mut self) -> &mut Self::Target {
+        impl PartialEq<$crate::__private::Vec<$inner>> for $name
+        where
+            for<'a> $inner: PartialEq,
+        {
+            fn eq(&self, other: &$crate::__private::Vec<$inner>) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<$name> for $crate::__private::Vec<$inner>
+        where
+            for<'a> $inner: PartialEq,
+        {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+
+        impl PartialEq<[$inner]> for $name
+        where
+            for<'a> $inner: PartialEq,
+        {
+            fn eq(&self, other: &[$inner]) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for [$inner]
+        where
+            for<'a> $inner: PartialEq,
+        {
+            fn eq(&self, other: &$name) -> bool {
+                self == other.0
+            }
+        }
+
+        impl<'b> PartialEq<&'b [$inner]> for $name
+        where
+            for<'a> $inner: PartialEq,
+        {
+            fn eq(&self, other: &&'b [$inner]) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl<'b> PartialEq<$name> for &'b [$inner]
+        where
+            for<'a> $inner: PartialEq,
+        {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+    };
+}
+
+/// Generates `AsRef<[$inner]>` and `AsMut<[$inner]>`.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// fn sum(numbers: &[u32]) -> u32 {
+///     numbers.iter().sum()
+/// }
+///
+/// let mut numbers = Numbers::new(vec![1, 2, 3]);
+/// numbers.as_mut().reverse();
+/// assert_eq!(numbers.as_slice(), [3, 2, 1]);
+/// assert_eq!(sum(numbers.as_ref()), 6);
+/// ```
+#[macro_export]
+macro_rules! impl_as_slice {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<$($param),*> AsRef<[$inner]> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn as_ref(&self) -> &[$inner] {
+                &self.0
+            }
+        }
+
+        impl<$($param),*> AsMut<[$inner]> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn as_mut(&mut self) -> &mut [$inner] {
                 &mut self.0
             }
         }
     };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_as_slice!($name, [], [], $inner);
+    };
 }
 
+/// Generates `drain_{variant}_into` methods, which move the payloads of a single variant into a sink.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// pub struct InvalidEmailError {
+///     email: String,
+/// }
+///
+/// #[derive(Debug)]
+/// pub struct PasswordMinLengthError;
+///
+/// #[derive(From, Debug)]
+/// pub enum ValidationError {
+///     InvalidEmail(InvalidEmailError),
+///     PasswordMinLength(PasswordMinLengthError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct ValidationErrors(Vec<ValidationError>);
+///     variants = [InvalidEmail(InvalidEmailError), PasswordMinLength(PasswordMinLengthError)];
+/// );
+///
+/// let mut errors = ValidationErrors::default();
+/// errors.push(InvalidEmailError { email: "a@example.com".into() });
+/// errors.push(PasswordMinLengthError);
+/// errors.push(InvalidEmailError { email: "b@example.com".into() });
+///
+/// let mut invalid_emails = vec![InvalidEmailError { email: "c@example.com".into() }];
+/// assert_eq!(errors.drain_invalid_email_into(&mut invalid_emails), 2);
+/// assert_eq!(invalid_emails.iter().map(|error| error.email.as_str()).collect::<Vec<_>>(), ["c@example.com", "a@example.com", "b@example.com"]);
+/// assert!(errors.is_only_password_min_length());
+///
+/// // The other variants are kept even if the sink stops consuming early
+/// #[derive(Default)]
+/// pub struct FirstOnly(Option<InvalidEmailError>);
+///
+/// impl Extend<InvalidEmailError> for FirstOnly {
+///     fn extend<T: IntoIterator<Item = InvalidEmailError>>(&mut self, iter: T) {
+///         self.0 = iter.into_iter().next();
+///     }
+/// }
+///
+/// errors.push(InvalidEmailError { email: "d@example.com".into() });
+/// errors.push(PasswordMinLengthError);
+/// errors.push(InvalidEmailError { email: "e@example.com".into() });
+/// let mut first = FirstOnly::default();
+/// assert_eq!(errors.drain_invalid_email_into(&mut first), 2);
+/// assert_eq!(first.0, Some(InvalidEmailError { email: "d@example.com".into() }));
+/// assert_eq!(errors.len(), 2);
+/// assert!(errors.is_only_password_min_length());
+/// ```
 #[macro_export]
-macro_rules! impl_from_vec {
+macro_rules! impl_drain_variant_into {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                $(
+                    #[doc = concat!("Removes the `", stringify!($kind), "` elements and extends the sink with their payloads, keeping the order. The other variants are kept as is, even if the sink doesn't consume every payload. Returns the number of payloads passed to the sink.")]
+                    pub fn [<drain_ $kind:snake _into>](&mut self, sink: &mut impl Extend<$payload>) -> usize {
+                        type __VecOfEnumInner = $inner;
+                        let mut rest = $crate::__private::Vec::with_capacity(self.0.len());
+                        let mut payloads = $crate::__private::Vec::new();
+                        for value in ::core::mem::take(&mut self.0) {
+                            match value {
+                                __VecOfEnumInner::$kind(payload) => payloads.push(payload),
+                                #[allow(unreachable_patterns)]
+                                value => rest.push(value),
+                            }
+                        }
+                        self.0 = rest;
+                        let moved = payloads.len();
+                        sink.extend(payloads);
+                        moved
+                    }
+                )+
+            }
+        }
+    };
+}
+
+/// Generates `into_sorted_vec`, `into_sorted` and their `_by` and `_by_key` variants, which consume the collection and return the sorted elements.
+///
+/// The sort is stable, so the equal elements keep the order in which they were added.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Numbers(Vec<u32>);
+/// );
+///
+/// assert_eq!(Numbers::new(vec![3, 1, 2]).into_sorted_vec(), [1, 2, 3]);
+/// assert_eq!(Numbers::new(vec![3, 1, 2]).into_sorted_vec_by(|a, b| b.cmp(a)), [3, 2, 1]);
+/// assert_eq!(Numbers::new(vec![13, 1, 22, 11]).into_sorted_vec_by_key(|number| number % 10), [1, 11, 22, 13]);
+///
+/// assert_eq!(Numbers::new(vec![3, 1, 2]).into_sorted().as_slice(), [1, 2, 3]);
+/// assert_eq!(Numbers::new(vec![3, 1, 2]).into_sorted_by(|a, b| b.cmp(a)).as_slice(), [3, 2, 1]);
+/// assert_eq!(Numbers::new(vec![13, 1, 22, 11]).into_sorted_by_key(|number| number % 10).as_slice(), [1, 11, 22, 13]);
+/// ```
+#[macro_export]
+macro_rules! impl_into_sorted {
     ($name:ident, $inner:ty) => {
-        impl From<Vec<$inner>> for $name {
-            fn from(vec: Vec<$inner>) -> Self {
-                Self(vec)
+        impl $name {
+            /// Returns the elements as a `Vec`, sorted. Requires the element type to implement `Ord`.
+            pub fn into_sorted_vec(self) -> $crate::__private::Vec<$inner>
+            where
+                for<'a> $inner: Ord,
+            {
+                let mut vec = $crate::__private::Vec::from(self);
+                vec.sort();
+                vec
+            }
+
+            /// Returns the elements as a `Vec`, sorted with the comparator.
+            pub fn into_sorted_vec_by(self, compare: impl FnMut(&$inner, &$inner) -> ::core::cmp::Ordering) -> $crate::__private::Vec<$inner> {
+                let mut vec = $crate::__private::Vec::from(self);
+                vec.sort_by(compare);
+                vec
+            }
+
+            /// Returns the elements as a `Vec`, sorted by the key.
+            pub fn into_sorted_vec_by_key<__VecOfEnumKey: Ord>(self, key: impl FnMut(&$inner) -> __VecOfEnumKey) -> $crate::__private::Vec<$inner> {
+                let mut vec = $crate::__private::Vec::from(self);
+                vec.sort_by_key(key);
+                vec
+            }
+
+            /// Returns the collection with the elements sorted. Requires the element type to implement `Ord`.
+            pub fn into_sorted(self) -> Self
+            where
+                for<'a> $inner: Ord,
+            {
+                Self(self.into_sorted_vec())
+            }
+
+            /// Returns the collection with the elements sorted with the comparator.
+            pub fn into_sorted_by(self, compare: impl FnMut(&$inner, &$inner) -> ::core::cmp::Ordering) -> Self {
+                Self(self.into_sorted_vec_by(compare))
+            }
+
+            /// Returns the collection with the elements sorted by the key.
+            pub fn into_sorted_by_key<__VecOfEnumKey: Ord>(self, key: impl FnMut(&$inner) -> __VecOfEnumKey) -> Self {
+                Self(self.into_sorted_vec_by_key(key))
             }
         }
     };
 }
 
+/// Generates `PartialOrd` and `Ord` impls for the `ord_by = ...;` option of [`define!`].
+///
+/// `ord_by = len` compares the lengths first, then the elements lexicographically (requires the element type to implement `Ord`).
 #[macro_export]
-macro_rules! impl_from_value {
-    ($name:ident, [$($value_source:ty),+]) => {
-        $(
-            $crate::impl_from_value!($name, $value_source);
-        )+
+macro_rules! impl_ord_by {
+    ($name:ident, $inner:ty, len) => {
+        impl PartialOrd for $name
+        where
+            for<'a> $inner: Ord,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name
+        where
+            for<'a> $inner: Ord,
+        {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                self.0
+                    .len()
+                    .cmp(&other.0.len())
+                    .then_with(|| self.0.cmp(&other.0))
+            }
+        }
     };
-    ($name:ident, $value_source:ty) => {
-        impl From<$value_source> for $name {
-            fn from(source: $value_source) -> Self {
-                Self(vec![source.into()])
+    ($name:ident, $inner:ty, $ord_by:ident) => {
+        compile_error!(concat!("unsupported `ord_by = ", stringify!($ord_by), "` option (the supported value is `len`)"));
+    };
+}
+
+/// Generates `find_duplicates`, which returns the elements that occur more than once.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let messages = Messages::new(vec!["is blocked", "is too long", "is blocked", "is empty", "is blocked", "is empty"]);
+/// assert_eq!(messages.find_duplicates(), [&"is blocked", &"is empty"]);
+/// assert!(Messages::new(vec!["is blocked"]).find_duplicates().is_empty());
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! impl_find_duplicates {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns the elements that occur at least twice, each once, in the order of their first occurrence. Requires the element type to implement `Eq` and `Hash`.
+            pub fn find_duplicates(&self) -> $crate::__private::Vec<&$inner>
+            where
+                for<'a> $inner: Eq + ::core::hash::Hash,
+            {
+                let mut counts = $crate::__private::HashMap::<&$inner, usize>::new();
+                self.0.iter().for_each(|value| {
+                    let count = counts.entry(value).or_default();
+                    // SAFETY: the count never exceeds the length of the vector, so the addition never saturates
+                    *count = count.saturating_add(1);
+                });
+                // Removing the count on the first occurrence reports every duplicate once
+                self.0
+                    .iter()
+                    .filter(|value| counts.remove(*value).is_some_and(|count| count > 1))
+                    .collect()
             }
         }
     };
 }
 
+#[cfg(not(feature = "std"))]
 #[macro_export]
-macro_rules! impl_into_vec {
+macro_rules! impl_find_duplicates {
+    ($name:ident, $inner:ty) => {};
+}
+
+/// Generates `find_duplicates_ord`, which returns the elements that occur more than once, like `find_duplicates`, but stores the counts in a `BTreeMap` (the element type must implement `Ord` instead of `Hash`).
+///
+/// Unlike `find_duplicates`, the method doesn't require the `std` feature.
+///
+/// ```rust
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let messages = Messages::new(vec!["is blocked", "is too long", "is blocked"]);
+/// assert_eq!(messages.find_duplicates_ord(), [&"is blocked"]);
+/// ```
+#[macro_export]
+macro_rules! impl_find_duplicates_ord {
+    ($name:ident, $inner:ty) => {
+        impl $name {
+            /// Returns the elements that occur at least twice, each once, in the order of their first occurrence. Requires the element type to implement `Ord`.
+            pub fn find_duplicates_ord(&self) -> $crate::__private::Vec<&$inner>
+            where
+                for<'a> $inner: Ord,
+            {
+                let mut counts = $crate::__private::BTreeMap::<&$inner, usize>::new();
+                self.0.iter().for_each(|value| {
+                    let count = counts.entry(value).or_default();
+                    // SAFETY: the count never exceeds the length of the vector, so the addition never saturates
+                    *count = count.saturating_add(1);
+                });
+                // Removing the count on the first occurrence reports every duplicate once
+                self.0
+                    .iter()
+                    .filter(|value| counts.remove(*value).is_some_and(|count| count > 1))
+                    .collect()
+            }
+        }
+    };
+}
+
+/// Generates the conversions between the wrapper and `VecDeque<$inner>` in both directions.
+///
+/// Both conversions reuse the allocation (converting a `VecDeque` into a `Vec` may move the elements within the buffer).
+///
+/// ```rust
+/// use std::collections::VecDeque;
+///
+/// vec_of_enum::define!(
+///     pub struct Messages(Vec<&'static str>);
+/// );
+///
+/// let mut queue = VecDeque::from(Messages::new(vec!["a", "b"]));
+/// assert_eq!(queue.pop_front(), Some("a"));
+/// queue.push_back("c");
+/// assert_eq!(Messages::from(queue).as_slice(), ["b", "c"]);
+/// ```
+#[macro_export]
+macro_rules! impl_vec_deque {
+    ($name:ident, [$($param:ident),*], [$($where_clause:tt)*], $inner:ty) => {
+        impl<$($param),*> From<$name<$($param),*>> for $crate::__private::VecDeque<$inner>
+        where $($where_clause)*
+        {
+            fn from(value: $name<$($param),*>) -> Self {
+                Self::from($crate::__private::Vec::from(value))
+            }
+        }
+
+        impl<$($param),*> From<$crate::__private::VecDeque<$inner>> for $name<$($param),*>
+        where $($where_clause)*
+        {
+            fn from(deque: $crate::__private::VecDeque<$inner>) -> Self {
+                Self::from($crate::__private::Vec::from(deque))
+            }
+        }
+    };
+    ($name:ident, $inner:ty) => {
+        $crate::impl_vec_deque!($name, [], [], $inner);
+    };
+}
+
+/// Generates `any_variant_matching` and `all_variant_matching`, which test the variant kinds of the elements with a predicate.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct FatalError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct WarningError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct NoticeError;
+/// #
+/// #[derive(From, Debug)]
+/// pub enum Problem {
+///     Fatal(FatalError),
+///     Warning(WarningError),
+///     Notice(NoticeError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Fatal(FatalError), Warning(WarningError), Notice(NoticeError)];
+/// );
+///
+/// fn is_fatal(kind: ProblemsVariantKind) -> bool {
+///     kind == ProblemsVariantKind::Fatal
+/// }
+///
+/// let mut problems = Problems::default();
+/// problems.push(WarningError);
+/// problems.push(NoticeError);
+/// assert!(!problems.any_variant_matching(is_fatal));
+/// assert!(problems.all_variant_matching(|kind| !is_fatal(kind)));
+///
+/// problems.push(FatalError);
+/// assert!(problems.any_variant_matching(is_fatal));
+/// assert!(!problems.all_variant_matching(|kind| !is_fatal(kind)));
+/// // Like `Iterator::all`, an empty collection matches any predicate
+/// assert!(Problems::default().all_variant_matching(is_fatal));
+/// ```
+#[macro_export]
+macro_rules! impl_variant_matching {
     ($name:ident, $inner:ty) => {
-        impl From<$name> for Vec<$inner> {
+        $crate::__private::paste! {
+            impl $name {
+                /// Returns `true` if the variant kind of at least one element satisfies the predicate.
+                pub fn any_variant_matching(&self, mut pred: impl FnMut([<$name VariantKind>]) -> bool) -> bool {
+                    self.0.iter().any(|value| pred(value.into()))
+                }
+
+                /// Returns `true` if the variant kinds of all elements satisfy the predicate (including when the collection is empty).
+                pub fn all_variant_matching(&self, mut pred: impl FnMut([<$name VariantKind>]) -> bool) -> bool {
+                    self.0.iter().all(|value| pred(value.into()))
+                }
+            }
+        }
+    };
+}
+
+/// Generates `impl From<$name> for $target` for the `into = Target => fn;` option of [`define!`].
+#[macro_export]
+macro_rules! impl_into_target {
+    ($name:ident, $inner:ty, $target:ty, $into:expr) => {
+        impl From<$name> for $target {
             fn from(value: $name) -> Self {
-                value.0
+                let into: fn($crate::__private::Vec<$inner>) -> $target = $into;
+                into($crate::__private::Vec::from(value))
             }
         }
     };
 }
+
+/// Generates `retain_frequent_variants`, which keeps only the elements of the variant kinds that occur at least a given number of times.
+///
+/// ```rust
+/// # use derive_more::From;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct TimeoutError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct DiskFullError;
+/// #
+/// # #[derive(Debug)]
+/// # pub struct NotFoundError;
+/// #
+/// #[derive(From, Debug)]
+/// pub enum Problem {
+///     Timeout(TimeoutError),
+///     DiskFull(DiskFullError),
+///     NotFound(NotFoundError),
+/// }
+///
+/// vec_of_enum::define!(
+///     pub struct Problems(Vec<Problem>);
+///     variants = [Timeout(TimeoutError), DiskFull(DiskFullError), NotFound(NotFoundError)];
+/// );
+///
+/// let mut problems = Problems::default();
+/// problems.push(TimeoutError);
+/// problems.push(DiskFullError);
+/// problems.push(TimeoutError);
+/// problems.push(NotFoundError);
+/// problems.push(TimeoutError);
+///
+/// assert_eq!(problems.retain_frequent_variants(2), 2);
+/// assert!(problems.is_only_timeout());
+/// assert_eq!(problems.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! impl_retain_frequent_variants {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {
+        $crate::__private::paste! {
+            impl $name {
+                /// Keeps only the elements whose variant kind occurs at least `min_count` times, counting the elements of every kind in the whole collection before removing any. Preserves the order of the kept elements. Returns the number of removed elements.
+                pub fn retain_frequent_variants(&mut self, min_count: usize) -> usize {
+                    type __VecOfEnumInner = $inner;
+                    $(let mut [<$kind:snake _count>] = 0usize;)+
+                    self.0.iter().for_each(|value| match value {
+                        // SAFETY: the count never exceeds the length of the vector, so the addition never saturates
+                        $(__VecOfEnumInner::$kind(_) => [<$kind:snake _count>] = [<$kind:snake _count>].saturating_add(1),)+
+                    });
+                    let len = self.0.len();
+                    self.0.retain(|value| match value {
+                        $(__VecOfEnumInner::$kind(_) => [<$kind:snake _count>] >= min_count,)+
+                    });
+                    // SAFETY: `retain` never increases the length, so the subtraction never saturates
+                    len.saturating_sub(self.0.len())
+                }
+            }
+        }
+    };
+}
+
+/// Generates a `Serialize` impl that represents the collection as a map from the variant names to the payloads of the elements of the variant, when the `serialize_grouped;` option is set (requires the `serde` feature).
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! impl_serialize_grouped {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {};
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+], ;) => {
+        $crate::__private::paste! {
+            impl $crate::__private::serde::Serialize for $name
+            where
+                $(for<'a> $payload: $crate::__private::serde::Serialize,)+
+            {
+                fn serialize<__VecOfEnumSerializer: $crate::__private::serde::Serializer>(&self, serializer: __VecOfEnumSerializer) -> Result<__VecOfEnumSerializer::Ok, __VecOfEnumSerializer::Error> {
+                    use $crate::__private::serde::ser::SerializeMap;
+                    type __VecOfEnumInner = $inner;
+                    $(let mut [<$kind:snake>] = $crate::__private::Vec::<&$payload>::new();)+
+                    self.0.iter().for_each(|value| match value {
+                        $(__VecOfEnumInner::$kind(payload) => [<$kind:snake>].push(payload),)+
+                    });
+                    let mut map = serializer.serialize_map(Some([$(stringify!($kind)),+].len()))?;
+                    $(map.serialize_entry(stringify!($kind), &[<$kind:snake>])?;)+
+                    map.end()
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+macro_rules! impl_serialize_grouped {
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+]) => {};
+    ($name:ident, $inner:ty, [$($kind:ident($payload:ty)),+], ;) => {
+        compile_error!("the `serialize_grouped` option requires the `serde` feature of `vec-of-enum`");
+    };
+}
+
+/// Generates a `#[test]` that checks `Default::default()` with the validator, for the `default_valid = validator;` option of [`define!`].
+///
+/// The validator takes a reference to the wrapper and returns `Result<(), E>` where `E: Debug`. The test is named `{name}_default_is_valid` (in snake case).
+#[macro_export]
+macro_rules! impl_default_valid {
+    ($name:ident, $validator:expr) => {
+        $crate::__private::paste! {
+            #[test]
+            fn [<$name:snake _default_is_valid>]() -> Result<(), impl ::core::fmt::Debug> {
+                // The wrapper type is inferred from the validator, so this works for generic wrappers too
+                ($validator)(&Default::default())
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::collections::BTreeMap;
+    pub use alloc::collections::BTreeSet;
+    pub use alloc::collections::VecDeque;
+    pub use alloc::fmt::format;
+    pub use alloc::string::String;
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    pub use std::collections::HashMap;
+    #[cfg(feature = "std")]
+    pub use std::collections::HashSet;
+    #[cfg(feature = "std")]
+    pub use std::io;
+
+    #[cfg(feature = "metrics")]
+    pub use metrics;
+    pub use paste::paste;
+    #[cfg(feature = "rayon")]
+    pub use rayon;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "serde_json")]
+    pub use serde_json;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+
+    #[cfg(feature = "serde")]
+    pub use crate::deserialize_extend::*;
+    #[cfg(feature = "serde")]
+    pub use crate::serialize_single_field::*;
+
+    pub use crate::debug_truncated::*;
+}
+
+#[cfg(feature = "serde")]
+mod deserialize_extend;
+
+#[cfg(feature = "serde")]
+mod serialize_single_field;
+
+mod debug_truncated;
+
+mod index_out_of_bounds;
+pub use index_out_of_bounds::*;
+
+mod pair;
+pub use pair::*;
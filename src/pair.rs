@@ -0,0 +1,22 @@
+/// An element that consists of a key and a value (implemented for 2-tuples). Used by the `paired` option of [`define!`](crate::define).
+pub trait Pair {
+    type Key;
+    type Value;
+
+    fn key(&self) -> &Self::Key;
+
+    fn value(&self) -> &Self::Value;
+}
+
+impl<K, V> Pair for (K, V) {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.0
+    }
+
+    fn value(&self) -> &V {
+        &self.1
+    }
+}
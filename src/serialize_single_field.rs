@@ -0,0 +1,86 @@
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{DeserializeSeed, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes `value` as a struct with a single field.
+pub fn serialize_single_field<S: Serializer, T: Serialize + ?Sized>(serializer: S, name: &'static str, field: &'static str, value: &T) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct(name, 1)?;
+    state.serialize_field(field, value)?;
+    state.end()
+}
+
+/// Deserializes the value of the single field of a struct, ignoring the other fields.
+pub fn deserialize_single_field<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D, name: &'static str, fields: &'static [&'static str; 1]) -> Result<T, D::Error> {
+    let [field] = *fields;
+    deserializer.deserialize_struct(
+        name,
+        fields,
+        SingleFieldVisitor {
+            name,
+            field,
+            marker: PhantomData,
+        },
+    )
+}
+
+struct SingleFieldVisitor<T> {
+    name: &'static str,
+    field: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SingleFieldVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "struct {} with field `{}`", self.name, self.field)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        seq.next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut value = None;
+        while let Some(is_field) = map.next_key_seed(FieldSeed(self.field))? {
+            if !is_field {
+                map.next_value::<IgnoredAny>()?;
+            } else if value.is_none() {
+                value = Some(map.next_value()?);
+            } else {
+                return Err(A::Error::duplicate_field(self.field));
+            }
+        }
+        value.ok_or_else(|| A::Error::missing_field(self.field))
+    }
+}
+
+/// Deserializes a struct key into `true` if it is equal to the expected field.
+struct FieldSeed(&'static str);
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl Visitor<'_> for FieldSeed {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a field identifier")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<bool, E> {
+        Ok(value == self.0)
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<bool, E> {
+        Ok(value == self.0.as_bytes())
+    }
+}
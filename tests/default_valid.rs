@@ -0,0 +1,34 @@
+#[derive(Debug)]
+pub struct TooLong;
+
+pub fn validate_len(messages: &Messages) -> Result<(), TooLong> {
+    if messages.len() > 1 { Err(TooLong) } else { Ok(()) }
+}
+
+vec_of_enum::define!(
+    pub struct Messages(Vec<String>);
+    default_valid = validate_len;
+);
+
+#[test]
+fn must_pass_for_a_valid_default() -> Result<(), String> {
+    messages_default_is_valid().map_err(|error| format!("{error:?}"))
+}
+
+// The tests generated inside a function body aren't collected by the test harness, so the generated test is called directly
+#[test]
+#[allow(unnameable_test_items)]
+fn must_fail_for_an_invalid_default() -> Result<(), &'static str> {
+    #[derive(Debug)]
+    pub struct Empty;
+
+    vec_of_enum::define!(
+        pub struct Required(Vec<String>);
+        default_valid = |required: &Required| if required.is_empty() { Err(Empty) } else { Ok(()) };
+    );
+
+    required_default_is_valid()
+        .err()
+        .map(|_| ())
+        .ok_or("the invalid default passed the validation")
+}